@@ -0,0 +1,183 @@
+// 答案文件（--answers / --save-answers）：记录初始化时每个问题的选择，便于在团队中重复生成相同的结构
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::de::{ DeserializeOwned, IntoDeserializer };
+use serde::{ Deserialize, Serialize };
+
+use crate::cli::InitArgs;
+use yuuskel::envfiles::EnvFormat;
+use yuuskel::error::{ Result, YuuskelError };
+use yuuskel::generate::PathStyle;
+use yuuskel::helpers::Helper;
+
+const HEADER: &str = "# yuuskel answers — replay with `yuuskel --answers <this file>`\n\n";
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Answers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    // "new"（新建文件夹）或 "here"（当前目录）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    // 前缀；false 表示不加前缀
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<Prefix>,
+    // 技术栈；"none" 表示不使用预设
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<bool>,
+    // SPDX 标识符；"none" 表示不生成 LICENSE
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holder: Option<String>,
+    // 以下不是问题，对应同名的命令行参数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gitkeep: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir_readmes: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env_paths: Option<PathStyle>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub env_formats: Vec<EnvFormat>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub helpers: Vec<Helper>,
+    // 模板问题的答案
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub vars: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Prefix {
+    Disabled(bool),
+    Name(String),
+}
+
+fn yes_no(value: bool) -> String {
+    (if value { "yes" } else { "no" }).to_string()
+}
+
+// 选项值与记录的答案之间的转换，写法与 yuuskel.toml 一致（如 relative、sh）
+pub fn to_answer(value: &impl Serialize) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn from_answer<T: DeserializeOwned>(answer: &str) -> Option<T> {
+    T::deserialize(IntoDeserializer::<serde::de::value::Error>::into_deserializer(answer)).ok()
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers> {
+        let content = fs::read_to_string(path).map_err(|e| YuuskelError::fs(path, e))?;
+        let answers: Answers = toml::from_str(&content).map_err(|e| YuuskelError::config(path, e))?;
+        if let Some(Prefix::Disabled(true)) = answers.prefix {
+            return Err(YuuskelError::config(path, "prefix: expected a string or false"));
+        }
+        Ok(answers)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let body = toml::to_string(self).unwrap_or_default();
+        fs::write(path, format!("{}{}", HEADER, body)).map_err(|e| YuuskelError::fs(path, e))
+    }
+
+    // 不是问题的选项填入命令行参数，命令行参数优先
+    pub fn fill_args(&self, args: &mut InitArgs) {
+        if args.template.is_none() {
+            args.template = self.template.clone();
+        }
+        args.gitkeep |= self.gitkeep.unwrap_or(false);
+        args.dir_readmes |= self.dir_readmes.unwrap_or(false);
+        args.env_paths = args.env_paths.or(self.env_paths);
+        args.env_formats.extend(&self.env_formats);
+        args.helpers.extend(&self.helpers);
+    }
+
+    // 转为按问题 key 索引的答案（见 prompt::Question）；取值在提问时校验
+    pub fn to_map(&self) -> BTreeMap<String, String> {
+        let mut map = BTreeMap::new();
+        let mut insert = |key: &str, value: Option<String>| {
+            if let Some(value) = value {
+                map.insert(key.to_string(), value);
+            }
+        };
+        insert("language", self.language.clone());
+        insert("mode", self.mode.clone());
+        insert("name", self.name.clone());
+        match &self.prefix {
+            Some(Prefix::Name(prefix)) => {
+                insert("use_prefix", Some(yes_no(true)));
+                insert("prefix", Some(prefix.clone()));
+            }
+            Some(Prefix::Disabled(_)) => insert("use_prefix", Some(yes_no(false))),
+            None => {}
+        }
+        insert("stack", self.stack.clone());
+        insert("git", self.git.map(yes_no));
+        insert("commit", self.commit.map(yes_no));
+        // 许可证统一为选项中的 SPDX（如 GPL-3.0-only → GPL-3.0），无法识别的保留原样以便报错
+        insert(
+            "license",
+            self.license
+                .as_ref()
+                .map(|id| yuuskel::license::find(id).map_or(id.clone(), |l| l.spdx.to_string()))
+        );
+        insert("holder", self.holder.clone());
+        for (key, value) in &self.vars {
+            map.insert(format!("var.{}", key), value.clone());
+        }
+        map
+    }
+
+    // 由会话中记录的答案（问题 key → 答案）生成
+    pub fn from_map(map: &BTreeMap<String, String>) -> Answers {
+        let get = |key: &str| map.get(key).cloned();
+        let flag = |key: &str| map.get(key).map(|v| v == "yes");
+        // 多个取值以逗号分隔
+        fn list<T: DeserializeOwned>(map: &BTreeMap<String, String>, key: &str) -> Vec<T> {
+            map.get(key)
+                .map(|v| v.split(',').filter_map(from_answer).collect())
+                .unwrap_or_default()
+        }
+        let prefix = match flag("use_prefix") {
+            Some(true) => get("prefix").map(Prefix::Name),
+            Some(false) => Some(Prefix::Disabled(false)),
+            None => None,
+        };
+        Answers {
+            language: get("language"),
+            mode: get("mode"),
+            name: get("name"),
+            template: get("template"),
+            prefix,
+            stack: get("stack"),
+            git: flag("git"),
+            commit: flag("commit"),
+            license: get("license"),
+            holder: get("holder"),
+            gitkeep: flag("gitkeep"),
+            dir_readmes: flag("dir_readmes"),
+            env_paths: map.get("env_paths").and_then(|v| from_answer(v)),
+            env_formats: list(map, "env_formats"),
+            helpers: list(map, "helpers"),
+            vars: map
+                .iter()
+                .filter_map(|(key, value)| Some((key.strip_prefix("var.")?.to_string(), value.clone())))
+                .collect(),
+        }
+    }
+}
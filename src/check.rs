@@ -0,0 +1,296 @@
+// `yuuskel check`：按 yuuskel.toml 校验项目结构，失败时以非零状态退出（可用于 CI）
+use std::fs;
+use std::path::{ Component, Path, PathBuf };
+
+use colored::*;
+use serde::Serialize;
+
+use crate::cli::{ CheckArgs, ReportFormat };
+use yuuskel::dotenv::Document;
+use yuuskel::error::Result;
+use yuuskel::generate;
+use yuuskel::metadata::{ FILE_NAME, Metadata };
+use yuuskel::{ Language, MsgKey };
+
+#[derive(Serialize)]
+pub struct Report {
+    pub project_root: String,
+    pub ok: bool,
+    pub checks: Vec<Finding>,
+}
+
+#[derive(Serialize)]
+pub struct Finding {
+    // 检查项类别：metadata / template / dir / env / project_root / prefix / gitignore
+    pub check: &'static str,
+    pub target: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub message: String,
+}
+
+impl Report {
+    fn pass(&mut self, check: &'static str, target: impl Into<String>) {
+        self.checks.push(Finding { check, target: target.into(), ok: true, message: String::new() });
+    }
+
+    fn fail(&mut self, check: &'static str, target: impl Into<String>, message: String) {
+        self.ok = false;
+        self.checks.push(Finding { check, target: target.into(), ok: false, message });
+    }
+}
+
+// 返回是否全部通过
+pub fn run(lang: Language, args: &CheckArgs) -> Result<bool> {
+    let report = check(lang, &args.dir)?;
+    match args.format {
+        ReportFormat::Json => {
+            // 报告只包含字符串与布尔值，序列化不会失败
+            let json = serde_json::to_string_pretty(&report).expect("report is serializable");
+            println!("{}", json);
+        }
+        ReportFormat::Text => print_report(lang, &report),
+    }
+    Ok(report.ok)
+}
+
+pub fn check(lang: Language, target_dir: &Path) -> Result<Report> {
+    let abs_str = generate::project_root(target_dir);
+    let mut report = Report { project_root: abs_str.clone(), ok: true, checks: Vec::new() };
+
+    let metadata = match Metadata::load(target_dir) {
+        Ok(Some(metadata)) => metadata,
+        Ok(None) => {
+            report.fail("metadata", FILE_NAME, lang.msg(MsgKey::NotYuuskelProject).replace("{}", FILE_NAME));
+            return Ok(report);
+        }
+        Err(e) => {
+            report.fail("metadata", FILE_NAME, e.message(lang));
+            return Ok(report);
+        }
+    };
+    report.pass("metadata", FILE_NAME);
+
+    // 目录
+    for d in &metadata.dirs {
+        if target_dir.join(d).is_dir() {
+            report.pass("dir", d.as_str());
+        } else {
+            report.fail("dir", d.as_str(), lang.msg(MsgKey::CheckDirMissing).to_string());
+        }
+    }
+
+    // .env
+    let env_path = target_dir.join(".env");
+    let env = match fs::read_to_string(&env_path) {
+        Ok(content) => Document::parse(&content),
+        Err(_) => {
+            report.fail("env", ".env", lang.msg(MsgKey::CheckEnvFileMissing).to_string());
+            Document::default()
+        }
+    };
+    let lookup = |key: &str| env.get(key);
+    let root = Path::new(&abs_str);
+
+    if env_path.exists() {
+        match lookup("PROJECT_ROOT") {
+            Some(value) if normalize(&resolve(value, root)) == normalize(root) => {
+                report.pass("project_root", "PROJECT_ROOT");
+            }
+            Some(value) => {
+                report.fail(
+                    "project_root",
+                    "PROJECT_ROOT",
+                    lang.msg(MsgKey::CheckRootMismatch).replacen("{}", value, 1).replacen("{}", &abs_str, 1)
+                );
+            }
+            None => {
+                report.fail(
+                    "project_root",
+                    "PROJECT_ROOT",
+                    lang.msg(MsgKey::CheckEnvKeyMissing).to_string()
+                );
+            }
+        }
+
+        let prefix = metadata.prefix
+            .as_deref()
+            .map(|p| format!("{}_", p))
+            .unwrap_or_default();
+        // 受管理的变量取自 yuuskel.toml；旧版本的记录中没有时才加载模板
+        let keys: Vec<String> = match metadata.env_vars() {
+            Ok(env) => env.into_iter().map(|var| var.key).collect(),
+            Err(e) => {
+                report.fail("template", metadata.template.clone(), e.message(lang));
+                Vec::new()
+            }
+        };
+        let expected: Vec<String> = keys
+            .iter()
+            .map(|key| format!("{}{}", prefix, key))
+            .collect();
+        for (name, key) in keys.iter().zip(&expected) {
+            match lookup(key) {
+                Some(value) => {
+                    let path = normalize(&resolve(value, root));
+                    if path.starts_with(normalize(root)) {
+                        report.pass("env", key.as_str());
+                    } else {
+                        report.fail(
+                            "env",
+                            key.as_str(),
+                            lang.msg(MsgKey::CheckEnvOutside).replace("{}", value)
+                        );
+                    }
+                }
+                None => {
+                    // 同名变量不带前缀或带单段的其他前缀（如 LAB_）存在 → 前缀不一致；
+                    // 其他受管理的变量（如 TEMP_ASSETS_DIR 之于 ASSETS_DIR）不算
+                    let suffix = format!("_{}", name);
+                    let other = env
+                        .keys()
+                        .filter(|k| !expected.iter().any(|e| e == *k))
+                        .find(|k| {
+                            *k == name.as_str() ||
+                                k
+                                    .strip_suffix(&suffix)
+                                    .is_some_and(|p| !p.is_empty() && !p.contains('_'))
+                        });
+                    match other {
+                        Some(found) =>
+                            report.fail(
+                                "prefix",
+                                key.clone(),
+                                lang
+                                    .msg(MsgKey::CheckPrefixMismatch)
+                                    .replacen("{}", key, 1)
+                                    .replacen("{}", found, 1)
+                            ),
+                        None =>
+                            report.fail(
+                                "env",
+                                key.as_str(),
+                                lang.msg(MsgKey::CheckEnvKeyMissing).to_string()
+                            ),
+                    }
+                }
+            }
+        }
+    }
+
+    // .gitignore 必须忽略 .env
+    match fs::read_to_string(target_dir.join(".gitignore")) {
+        Ok(content) if ignores(&content, ".env") => report.pass("gitignore", ".gitignore"),
+        Ok(_) =>
+            report.fail("gitignore", ".gitignore", lang.msg(MsgKey::CheckGitignoreEnv).to_string()),
+        Err(_) =>
+            report.fail(
+                "gitignore",
+                ".gitignore",
+                lang.msg(MsgKey::CheckGitignoreMissing).to_string()
+            ),
+    }
+
+    Ok(report)
+}
+
+fn print_report(lang: Language, report: &Report) {
+    println!("{}{}", lang.msg(MsgKey::CheckTitle), report.project_root.cyan());
+    for finding in &report.checks {
+        if finding.ok {
+            println!("  {} {:<12} {}", "✔".green(), finding.check.dimmed(), finding.target);
+        } else {
+            println!(
+                "  {} {:<12} {} — {}",
+                "✘".red(),
+                finding.check.dimmed(),
+                finding.target.yellow(),
+                finding.message
+            );
+        }
+    }
+    let failed = report.checks
+        .iter()
+        .filter(|f| !f.ok)
+        .count();
+    let total = report.checks.len().to_string();
+    if failed == 0 {
+        println!("\n{}", lang.msg(MsgKey::CheckPassed).replace("{}", &total).green().bold());
+    } else {
+        let msg = lang
+            .msg(MsgKey::CheckFailed)
+            .replacen("{}", &failed.to_string(), 1)
+            .replacen("{}", &total, 1);
+        println!("\n{}", msg.red().bold());
+    }
+}
+
+// 支持绝对路径、相对项目根目录的路径以及 ${PROJECT_ROOT} 插值
+fn resolve(value: &str, root: &Path) -> PathBuf {
+    let root_str = root.to_string_lossy();
+    let value = value.replace("${PROJECT_ROOT}", &root_str).replace("$PROJECT_ROOT", &root_str);
+    let path = PathBuf::from(value);
+    if path.is_absolute() { path } else { root.join(path) }
+}
+
+// 去掉 . 与 ..（仅做词法处理，不访问磁盘）
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+// .gitignore 是否忽略给定的根目录文件（按 git 规则，后面的模式覆盖前面的）
+fn ignores(gitignore: &str, file: &str) -> bool {
+    let mut ignored = false;
+    for line in gitignore.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (negated, pattern) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+        if !pattern.ends_with('/') && glob_match(pattern, file) {
+            ignored = !negated;
+        }
+    }
+    ignored
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let (mut star, mut mark) = (None, 0);
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some(pi);
+            mark = ti;
+            pi += 1;
+        } else if let Some(s) = star {
+            pi = s + 1;
+            mark += 1;
+            ti = mark;
+        } else {
+            return false;
+        }
+    }
+    while pi < p.len() && p[pi] == '*' {
+        pi += 1;
+    }
+    pi == p.len()
+}
@@ -0,0 +1,312 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use clap::{ Args, Parser, Subcommand, ValueEnum };
+
+use yuuskel::Language;
+use yuuskel::envfiles::EnvFormat;
+use yuuskel::generate::PathStyle;
+use yuuskel::helpers::Helper;
+use yuuskel::stack::Stack;
+
+// 命令行参数：每个交互问题都有对应的参数，给出即跳过提示
+#[derive(Parser)]
+#[command(
+    name = "yuuskel",
+    version,
+    about = "yuuskel — Initialize standardized project structure",
+    after_help = "Any question answered on the command line is not asked interactively.\nWithout a terminal, every remaining question needs a flag (or --yes).",
+    args_conflicts_with_subcommands = true
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub init: InitArgs,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Bring an existing project up to date using the answers recorded in yuuskel.toml
+    Upgrade(UpgradeArgs),
+    /// Validate a project against its recorded skeleton (exits non-zero on failure)
+    Check(CheckArgs),
+    /// Rewrite the managed .env paths after the project has moved
+    #[command(alias = "sync-env")]
+    Relocate(RelocateArgs),
+    /// Manage templates fetched from git (git+<URL>)
+    Template(TemplateArgs),
+}
+
+#[derive(Args)]
+pub struct TemplateArgs {
+    #[command(subcommand)]
+    pub command: TemplateCommand,
+
+    /// Interface language
+    #[arg(long, value_enum, global = true)]
+    pub lang: Option<Language>,
+}
+
+#[derive(Subcommand)]
+pub enum TemplateCommand {
+    /// List built-in templates and the remote templates in the cache
+    List,
+    /// Print a template's template.toml
+    Show {
+        /// Template name, directory or git+<URL> (defaults to the configured template)
+        source: Option<String>,
+
+        /// Print the result of resolving `extends` instead of the file as written
+        #[arg(long)]
+        resolved: bool,
+    },
+    /// Fetch the latest commit of a remote template (all cached ones by default)
+    Update {
+        /// git+<URL> or the name shown by `template list`
+        source: Option<String>,
+    },
+    /// Delete a remote template from the cache
+    Remove {
+        /// git+<URL> or the name shown by `template list`
+        source: String,
+    },
+}
+
+#[derive(Args)]
+pub struct RelocateArgs {
+    /// Project directory
+    #[arg(default_value = ".")]
+    pub dir: PathBuf,
+
+    /// Interface language (defaults to the one recorded in yuuskel.toml)
+    #[arg(long, value_enum)]
+    pub lang: Option<Language>,
+
+    /// How paths are written into .env (defaults to the recorded style)
+    #[arg(long, value_enum, value_name = "STYLE")]
+    pub env_paths: Option<PathStyle>,
+
+    /// Print the planned changes without touching the disk
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Args)]
+pub struct CheckArgs {
+    /// Project directory
+    #[arg(default_value = ".")]
+    pub dir: PathBuf,
+
+    /// Interface language (defaults to the one recorded in yuuskel.toml)
+    #[arg(long, value_enum)]
+    pub lang: Option<Language>,
+
+    /// Report format
+    #[arg(long, value_enum, default_value = "text")]
+    pub format: ReportFormat,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ReportFormat {
+    Text,
+    Json,
+}
+
+#[derive(Args)]
+pub struct UpgradeArgs {
+    /// Project directory
+    #[arg(default_value = ".")]
+    pub dir: PathBuf,
+
+    /// Interface language (defaults to the one recorded in yuuskel.toml)
+    #[arg(long, value_enum)]
+    pub lang: Option<Language>,
+
+    /// User config file (default: $XDG_CONFIG_HOME/yuuskel/config.toml)
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Print the planned changes without touching the disk
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Args, Default)]
+pub struct InitArgs {
+    /// Interface and document language
+    #[arg(long, value_enum)]
+    pub lang: Option<Language>,
+
+    /// Initialize in the current directory
+    #[arg(long, conflicts_with = "name")]
+    pub here: bool,
+
+    /// Create a new project folder with this name (or path)
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// Prefix env vars with PREFIX_ (e.g. MYPROJ_OUTPUT_DIR)
+    #[arg(long, conflicts_with = "no_prefix")]
+    pub prefix: Option<String>,
+
+    /// Use generic env var names (e.g. OUTPUT_DIR)
+    #[arg(long)]
+    pub no_prefix: bool,
+
+    /// Initialize a Git repository
+    #[arg(long, overrides_with = "no_git")]
+    pub git: bool,
+
+    /// Do not initialize a Git repository
+    #[arg(long)]
+    pub no_git: bool,
+
+    /// Create an initial commit after `git init`
+    #[arg(long, overrides_with = "no_commit")]
+    pub commit: bool,
+
+    /// Do not create an initial commit
+    #[arg(long)]
+    pub no_commit: bool,
+
+    /// License to generate (SPDX identifier, or "none")
+    #[arg(long, value_name = "SPDX", value_parser = yuuskel::license::parse_arg)]
+    pub license: Option<String>,
+
+    /// Copyright holder written into LICENSE (defaults to `git config user.name`)
+    #[arg(long, value_name = "NAME")]
+    pub holder: Option<String>,
+
+    /// Skeleton template: built-in name or a directory containing template.toml
+    #[arg(long, value_name = "NAME|PATH")]
+    pub template: Option<String>,
+
+    /// Answer a template-defined question (repeatable)
+    #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var)]
+    pub vars: Vec<(String, String)>,
+
+    /// User config file (default: $XDG_CONFIG_HOME/yuuskel/config.toml)
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// How paths are written into .env
+    #[arg(long, value_enum, value_name = "STYLE")]
+    pub env_paths: Option<PathStyle>,
+
+    /// Add a language stack preset (manifest in src/, .gitignore entries, USAGE.md section)
+    #[arg(long, value_enum, conflicts_with = "no_stack")]
+    pub stack: Option<Stack>,
+
+    /// Keep the generic skeleton without a stack preset
+    #[arg(long)]
+    pub no_stack: bool,
+
+    /// Put a .gitkeep in every directory so the skeleton survives cloning
+    #[arg(long)]
+    pub gitkeep: bool,
+
+    /// Write a short README.md describing each directory's role
+    #[arg(long)]
+    pub dir_readmes: bool,
+
+    /// Also generate env.sh / env.ps1 / env.fish / .envrc / paths.json / paths.toml
+    #[arg(long = "env-format", value_enum, value_name = "FORMAT", value_delimiter = ',')]
+    pub env_formats: Vec<EnvFormat>,
+
+    /// Generate a typed paths module: src/paths.py / paths.rs / paths.jl / paths.R
+    #[arg(long = "helper", value_enum, value_name = "LANG", value_delimiter = ',')]
+    pub helpers: Vec<Helper>,
+
+    /// Print the planned changes without touching the disk
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Accept the default answer for every question not given as a flag
+    #[arg(short, long)]
+    pub yes: bool,
+
+    /// Read answers from a TOML file (flags take precedence)
+    #[arg(long = "answers", value_name = "PATH")]
+    pub answers_file: Option<PathBuf>,
+
+    /// Write this session's answers to a TOML file that `--answers` can replay
+    #[arg(long, value_name = "PATH", num_args = 0..=1, default_missing_value = "answers.toml")]
+    pub save_answers: Option<PathBuf>,
+}
+
+impl InitArgs {
+    pub fn here(&self) -> Option<bool> {
+        flag_pair(self.here, self.name.is_some())
+    }
+
+    pub fn git(&self) -> Option<bool> {
+        flag_pair(self.git, self.no_git)
+    }
+
+    pub fn commit(&self) -> Option<bool> {
+        flag_pair(self.commit, self.no_commit)
+    }
+
+    // 通过参数给出的答案，键为问题的 key（见 prompt::Question）
+    pub fn answers(&self) -> BTreeMap<String, String> {
+        let yes_no = |value: bool| (if value { "yes" } else { "no" }).to_string();
+        let mut answers = BTreeMap::new();
+        if let Some(lang) = self.lang {
+            answers.insert("language".to_string(), lang.code().to_string());
+        }
+        if let Some(here) = self.here() {
+            answers.insert("mode".to_string(), (if here { "here" } else { "new" }).to_string());
+        }
+        if let Some(name) = &self.name {
+            answers.insert("name".to_string(), name.clone());
+        }
+        if self.no_prefix {
+            answers.insert("use_prefix".to_string(), yes_no(false));
+        } else if let Some(prefix) = &self.prefix {
+            answers.insert("use_prefix".to_string(), yes_no(true));
+            answers.insert("prefix".to_string(), prefix.clone());
+        }
+        if self.no_stack {
+            answers.insert("stack".to_string(), "none".to_string());
+        } else if let Some(stack) = self.stack {
+            answers.insert("stack".to_string(), stack.name().to_string());
+        }
+        for (key, value) in &self.vars {
+            answers.insert(format!("var.{}", key), value.clone());
+        }
+        if let Some(git) = self.git() {
+            answers.insert("git".to_string(), yes_no(git));
+        }
+        if let Some(commit) = self.commit() {
+            answers.insert("commit".to_string(), yes_no(commit));
+        }
+        // 许可证统一为选项中的 SPDX（如 GPL-3.0-only → GPL-3.0）
+        if let Some(id) = &self.license {
+            let spdx = yuuskel::license::find(id).map_or("none", |l| l.spdx);
+            answers.insert("license".to_string(), spdx.to_string());
+        }
+        if let Some(holder) = &self.holder {
+            answers.insert("holder".to_string(), holder.clone());
+        }
+        answers
+    }
+}
+
+fn parse_var(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, val)) if !key.trim().is_empty() =>
+            Ok((key.trim().to_string(), val.to_string())),
+        _ => Err(format!("expected KEY=VALUE, got '{}'", value)),
+    }
+}
+
+// --xxx / --no-xxx 成对参数 → 三态（未指定时为 None）
+fn flag_pair(on: bool, off: bool) -> Option<bool> {
+    match (on, off) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
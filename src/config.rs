@@ -0,0 +1,76 @@
+use std::env;
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use serde::Deserialize;
+
+use yuuskel::Language;
+use yuuskel::envfiles::EnvFormat;
+use yuuskel::error::{ Result, YuuskelError };
+use yuuskel::helpers::Helper;
+use yuuskel::stack::Stack;
+
+// 用户级配置（$XDG_CONFIG_HOME/yuuskel/config.toml），作为各问题的默认答案
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub language: Option<Language>,
+    pub license: Option<String>,
+    pub template: Option<String>,
+    pub extra_dirs: Vec<String>,
+    pub stack: Option<Stack>,
+    pub gitkeep: bool,
+    pub dir_readmes: bool,
+    pub env_formats: Vec<EnvFormat>,
+    pub helpers: Vec<Helper>,
+    pub author: Author,
+    pub git: Git,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Author {
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Git {
+    pub init: Option<bool>,
+    pub commit: Option<bool>,
+}
+
+// 配置目录：$XDG_CONFIG_HOME/yuuskel，其次 %APPDATA%\yuuskel（Windows）或 ~/.config/yuuskel
+pub fn config_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty()) {
+        return Some(PathBuf::from(dir).join("yuuskel"));
+    }
+    #[cfg(windows)]
+    if let Some(dir) = env::var_os("APPDATA").filter(|v| !v.is_empty()) {
+        return Some(PathBuf::from(dir).join("yuuskel"));
+    }
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".config").join("yuuskel"))
+}
+
+impl Config {
+    // 读取配置；未指定路径且默认位置不存在时返回空配置
+    pub fn load(path: Option<&Path>) -> Result<Config> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None =>
+                match config_dir().map(|dir| dir.join("config.toml")) {
+                    Some(path) if path.is_file() => path,
+                    _ => {
+                        return Ok(Config::default());
+                    }
+                }
+        };
+        let content = fs::read_to_string(&path).map_err(|e| YuuskelError::fs(&path, e))?;
+        let config: Config = toml::from_str(&content).map_err(|e| YuuskelError::config(&path, e))?;
+        if let Some(id) = &config.license {
+            yuuskel::license::parse_arg(id).map_err(|e| YuuskelError::config(&path, e))?;
+        }
+        Ok(config)
+    }
+}
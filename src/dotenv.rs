@@ -0,0 +1,400 @@
+// .env 的无损解析与写回：保留注释、空行、export 前缀、引号风格与行内注释，
+// 未改动的内容按原样（逐字节）输出
+use std::fmt;
+
+#[derive(Debug, Default)]
+pub struct Document {
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug)]
+enum Entry {
+    // 空行、注释或无法解析的行，原样保留（含换行符）
+    Raw(String),
+    Pair(Pair),
+}
+
+#[derive(Debug)]
+struct Pair {
+    // 原始文本（含换行符）；修改后为 None，按各部分重新拼接
+    raw: Option<String>,
+    // key 之前的空白与 export 关键字，例如 "  export "
+    lead: String,
+    key: String,
+    // key 与值之间的部分，例如 " = "
+    sep: String,
+    value: String,
+    quote: Quote,
+    // 值之后的内容（空白与行内注释）
+    trail: String,
+    // 行尾换行符："\n"、"\r\n" 或 ""（文件末尾）
+    eol: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Quote {
+    None,
+    Single,
+    Double,
+}
+
+impl Document {
+    pub fn parse(input: &str) -> Document {
+        let mut entries = Vec::new();
+        let mut rest = input;
+        while !rest.is_empty() {
+            let (entry, consumed) = parse_entry(rest);
+            entries.push(entry);
+            rest = &rest[consumed..];
+        }
+        Document { entries }
+    }
+
+    // 取值（同名变量以最后一次出现为准）
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.pairs()
+            .rev()
+            .find(|p| p.key == key)
+            .map(|p| p.value.as_str())
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.pairs().map(|p| p.key.as_str())
+    }
+
+    // 原位更新第一次出现的变量并删除其后的重复项；不存在时追加到末尾。
+    // 值不变时不做任何改动。返回是否有改动
+    pub fn set(&mut self, key: &str, value: &str) -> bool {
+        let positions: Vec<usize> = self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| matches!(e, Entry::Pair(p) if p.key == key))
+            .map(|(i, _)| i)
+            .collect();
+
+        let Some((&first, duplicates)) = positions.split_first() else {
+            self.append(key, value);
+            return true;
+        };
+
+        let mut changed = false;
+        for &i in duplicates.iter().rev() {
+            self.entries.remove(i);
+            changed = true;
+        }
+        if let Entry::Pair(pair) = &mut self.entries[first] && pair.value != value {
+            pair.value = value.to_string();
+            pair.raw = None;
+            if pair.quote == Quote::Single && value.contains('\'') {
+                pair.quote = Quote::Double;
+            }
+            if pair.quote == Quote::None && needs_quotes(value) {
+                pair.quote = Quote::Double;
+            }
+            changed = true;
+        }
+        changed
+    }
+
+    fn pairs(&self) -> impl DoubleEndedIterator<Item = &Pair> {
+        self.entries.iter().filter_map(|e| {
+            match e {
+                Entry::Pair(p) => Some(p),
+                Entry::Raw(_) => None,
+            }
+        })
+    }
+
+    fn append(&mut self, key: &str, value: &str) {
+        // 末行没有换行符时先补上
+        match self.entries.last_mut() {
+            Some(Entry::Raw(raw)) if !raw.ends_with('\n') => raw.push('\n'),
+            Some(Entry::Pair(pair)) if pair.eol.is_empty() => {
+                pair.eol = "\n".to_string();
+                if let Some(raw) = &mut pair.raw {
+                    raw.push('\n');
+                }
+            }
+            _ => {}
+        }
+        self.entries.push(
+            Entry::Pair(Pair {
+                raw: None,
+                lead: String::new(),
+                key: key.to_string(),
+                sep: "=".to_string(),
+                value: value.to_string(),
+                quote: Quote::Double,
+                trail: String::new(),
+                eol: "\n".to_string(),
+            })
+        );
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            match entry {
+                Entry::Raw(raw) => f.write_str(raw)?,
+                Entry::Pair(pair) => {
+                    match &pair.raw {
+                        Some(raw) => f.write_str(raw)?,
+                        None => {
+                            write!(
+                                f,
+                                "{}{}{}{}{}{}",
+                                pair.lead,
+                                pair.key,
+                                pair.sep,
+                                encode(&pair.value, pair.quote),
+                                pair.trail,
+                                pair.eol
+                            )?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// 当前行（或跨行引号值）的长度与换行符
+fn split_line(input: &str) -> (&str, &str) {
+    match input.find('\n') {
+        Some(i) if i > 0 && input.as_bytes()[i - 1] == b'\r' => (&input[..i - 1], "\r\n"),
+        Some(i) => (&input[..i], "\n"),
+        None => (input, ""),
+    }
+}
+
+fn parse_entry(input: &str) -> (Entry, usize) {
+    let (line, eol) = split_line(input);
+    let line_len = line.len() + eol.len();
+    let raw_line = || (Entry::Raw(input[..line_len].to_string()), line_len);
+
+    let body = line.trim_start();
+    if body.is_empty() || body.starts_with('#') {
+        return raw_line();
+    }
+
+    // [空白][export 空白]KEY[空白]=[空白]VALUE
+    let mut lead_len = line.len() - body.len();
+    let mut after_lead = body;
+    if let Some(rest) = body.strip_prefix("export") {
+        let trimmed = rest.trim_start();
+        if trimmed.len() < rest.len() {
+            lead_len += "export".len() + (rest.len() - trimmed.len());
+            after_lead = trimmed;
+        }
+    }
+    let key_len = after_lead
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-'))
+        .unwrap_or(after_lead.len());
+    if key_len == 0 {
+        return raw_line();
+    }
+    let key = &after_lead[..key_len];
+    let after_key = &after_lead[key_len..];
+    let before_eq = after_key.trim_start();
+    let Some(after_eq) = before_eq.strip_prefix('=') else {
+        return raw_line();
+    };
+    let value_start = after_eq.trim_start_matches([' ', '\t']);
+    let sep_len = after_key.len() - value_start.len();
+
+    let offset = lead_len + key_len + sep_len; // 值在 input 中的起始位置
+    let lead = &line[..lead_len];
+    let sep = &after_key[..sep_len];
+
+    let (value, quote, value_len) = match value_start.chars().next() {
+        Some(q @ ('"' | '\'')) => {
+            // 引号值可以跨行，需从 input 中继续读取
+            match find_closing(&input[offset + 1..], q) {
+                Some(end) => {
+                    let inner = &input[offset + 1..offset + 1 + end];
+                    let (value, quote) = if q == '"' {
+                        (unescape(inner), Quote::Double)
+                    } else {
+                        (inner.to_string(), Quote::Single)
+                    };
+                    (value, quote, end + 2)
+                }
+                None => {
+                    return raw_line();
+                }
+            }
+        }
+        _ => {
+            // 无引号：到行内注释（空白 + #）或行尾为止
+            let end = value_start
+                .char_indices()
+                .find(|&(i, c)| {
+                    c == '#' && i > 0 && value_start[..i].ends_with([' ', '\t'])
+                })
+                .map_or(value_start.len(), |(i, _)| i);
+            let value = value_start[..end].trim_end();
+            (value.to_string(), Quote::None, value.len())
+        }
+    };
+
+    // 值之后到行尾的剩余部分
+    let value_end = offset + value_len;
+    let (tail_line, eol) = split_line(&input[value_end..]);
+    let consumed = value_end + tail_line.len() + eol.len();
+    let pair = Pair {
+        raw: Some(input[..consumed].to_string()),
+        lead: lead.to_string(),
+        key: key.to_string(),
+        sep: sep.to_string(),
+        value,
+        quote,
+        trail: tail_line.to_string(),
+        eol: eol.to_string(),
+    };
+    (Entry::Pair(pair), consumed)
+}
+
+// 找到未转义的结束引号（单引号内不处理转义）
+fn find_closing(input: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in input.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' && quote == '"' {
+            escaped = true;
+        } else if c == quote {
+            return Some(i);
+        }
+    }
+    None
+}
+
+fn unescape(inner: &str) -> String {
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some(c @ ('"' | '\\' | '$')) => out.push(c),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+fn needs_quotes(value: &str) -> bool {
+    value.is_empty() || value.contains(|c: char| c.is_whitespace() || "#\"'\\".contains(c))
+}
+
+fn encode(value: &str, quote: Quote) -> String {
+    match quote {
+        Quote::None => value.to_string(),
+        Quote::Single => format!("'{}'", value),
+        Quote::Double => {
+            let escaped = value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+            format!("\"{}\"", escaped)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str =
+        "# Project paths\n\nexport OUTPUT_DIR=/old/output\n  INPUT_DIR = \"/old/input\"  # raw data\nNAME='single quoted'\nMULTI=\"line one\nline two\"\nnot a pair\r\nLAST=1";
+
+    #[test]
+    fn round_trips_untouched_content() {
+        let doc = Document::parse(SAMPLE);
+        assert_eq!(doc.to_string(), SAMPLE);
+    }
+
+    #[test]
+    fn parses_export_spacing_quotes_and_comments() {
+        let doc = Document::parse(SAMPLE);
+        assert_eq!(doc.get("OUTPUT_DIR"), Some("/old/output"));
+        assert_eq!(doc.get("INPUT_DIR"), Some("/old/input"));
+        assert_eq!(doc.get("NAME"), Some("single quoted"));
+        assert_eq!(doc.get("MULTI"), Some("line one\nline two"));
+        assert_eq!(doc.get("LAST"), Some("1"));
+        assert_eq!(doc.keys().collect::<Vec<_>>(), [
+            "OUTPUT_DIR",
+            "INPUT_DIR",
+            "NAME",
+            "MULTI",
+            "LAST",
+        ]);
+    }
+
+    #[test]
+    fn updates_in_place_preserving_style() {
+        let mut doc = Document::parse(SAMPLE);
+        assert!(doc.set("OUTPUT_DIR", "/new/output"));
+        assert!(doc.set("INPUT_DIR", "/new/input"));
+        let out = doc.to_string();
+        assert!(out.contains("\nexport OUTPUT_DIR=/new/output\n"));
+        assert!(out.contains("\n  INPUT_DIR = \"/new/input\"  # raw data\n"));
+        assert!(out.starts_with("# Project paths\n\n"));
+        assert!(out.ends_with("not a pair\r\nLAST=1"));
+    }
+
+    #[test]
+    fn unchanged_value_keeps_bytes() {
+        let mut doc = Document::parse(SAMPLE);
+        assert!(!doc.set("INPUT_DIR", "/old/input"));
+        assert_eq!(doc.to_string(), SAMPLE);
+    }
+
+    #[test]
+    fn appends_missing_keys_with_newline() {
+        let mut doc = Document::parse("A=1");
+        assert!(doc.set("B", "two words"));
+        assert_eq!(doc.to_string(), "A=1\nB=\"two words\"\n");
+    }
+
+    #[test]
+    fn removes_duplicates_after_first() {
+        let mut doc = Document::parse("A=1\n# keep\nA=2\nB=3\n");
+        assert_eq!(doc.get("A"), Some("2"));
+        assert!(doc.set("A", "9"));
+        assert_eq!(doc.to_string(), "A=9\n# keep\nB=3\n");
+    }
+
+    #[test]
+    fn quotes_unquoted_value_when_needed() {
+        let mut doc = Document::parse("A=plain\nB='x'\n");
+        doc.set("A", "has space");
+        doc.set("B", "it's");
+        assert_eq!(doc.to_string(), "A=\"has space\"\nB=\"it's\"\n");
+    }
+
+    #[test]
+    fn escapes_and_unescapes_double_quotes() {
+        let mut doc = Document::parse("A=\"say \\\"hi\\\"\"\n");
+        assert_eq!(doc.get("A"), Some("say \"hi\""));
+        doc.set("A", "back\\slash");
+        assert_eq!(doc.to_string(), "A=\"back\\\\slash\"\n");
+    }
+
+    #[test]
+    fn unterminated_quote_is_kept_raw() {
+        let input = "A=\"never closed\nB=2\n";
+        let doc = Document::parse(input);
+        assert_eq!(doc.get("A"), None);
+        assert_eq!(doc.get("B"), Some("2"));
+        assert_eq!(doc.to_string(), input);
+    }
+}
@@ -0,0 +1,104 @@
+// .env 之外的环境文件：与 .env 来自同一份 env 表，由 yuuskel 整体生成并在增量更新时同步
+use serde::{ Deserialize, Serialize };
+
+use crate::generate::PathStyle;
+
+const HEADER: &str = "Generated by yuuskel; `yuuskel upgrade` / `yuuskel relocate` overwrite manual edits";
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum EnvFormat {
+    // POSIX shell：source env.sh
+    Sh,
+    // PowerShell：. ./env.ps1
+    Ps1,
+    // fish：source env.fish
+    Fish,
+    // direnv：.envrc
+    Direnv,
+    // paths.json
+    Json,
+    // paths.toml
+    Toml,
+}
+
+impl EnvFormat {
+    pub fn file_name(self) -> &'static str {
+        match self {
+            EnvFormat::Sh => "env.sh",
+            EnvFormat::Ps1 => "env.ps1",
+            EnvFormat::Fish => "env.fish",
+            EnvFormat::Direnv => ".envrc",
+            EnvFormat::Json => "paths.json",
+            EnvFormat::Toml => "paths.toml",
+        }
+    }
+
+    // vars：(键, 相对项目根目录的路径)，PROJECT_ROOT 对应 None。
+    // 脚本格式在非绝对路径风格下引用 PROJECT_ROOT；JSON/TOML 不支持插值，始终写绝对路径
+    pub fn render(self, vars: &[(String, Option<String>)], abs_str: &str, style: PathStyle) -> String {
+        let interpolate = style != PathStyle::Absolute;
+        let absolute = |path: &Option<String>| {
+            match path {
+                Some(p) => format!("{}/{}", abs_str, p),
+                None => abs_str.to_string(),
+            }
+        };
+        let mut out = String::new();
+        match self {
+            EnvFormat::Sh | EnvFormat::Direnv => {
+                out.push_str(&format!("# {}\n", HEADER));
+                for (key, path) in vars {
+                    let value = match path {
+                        Some(p) if interpolate => format!("\"${{PROJECT_ROOT}}/{}\"", escape_double(p)),
+                        _ => format!("'{}'", absolute(path).replace('\'', "'\\''")),
+                    };
+                    out.push_str(&format!("export {}={}\n", key, value));
+                }
+            }
+            EnvFormat::Ps1 => {
+                out.push_str(&format!("# {}\n", HEADER));
+                for (key, path) in vars {
+                    let value = match path {
+                        Some(p) if interpolate => {
+                            format!("\"$env:PROJECT_ROOT/{}\"", p.replace('`', "``").replace('"', "`\"").replace('$', "`$"))
+                        }
+                        _ => format!("'{}'", absolute(path).replace('\'', "''")),
+                    };
+                    out.push_str(&format!("$env:{} = {}\n", key, value));
+                }
+            }
+            EnvFormat::Fish => {
+                out.push_str(&format!("# {}\n", HEADER));
+                for (key, path) in vars {
+                    let value = match path {
+                        Some(p) if interpolate => format!("\"$PROJECT_ROOT/{}\"", escape_double(p)),
+                        _ => format!("'{}'", absolute(path).replace('\\', "\\\\").replace('\'', "\\'")),
+                    };
+                    out.push_str(&format!("set -gx {} {}\n", key, value));
+                }
+            }
+            EnvFormat::Json => {
+                let entries: Vec<String> = vars
+                    .iter()
+                    .map(|(key, path)| {
+                        format!("  {}: {}", serde_json::Value::from(key.as_str()), serde_json::Value::from(absolute(path)))
+                    })
+                    .collect();
+                out.push_str(&format!("{{\n{}\n}}\n", entries.join(",\n")));
+            }
+            EnvFormat::Toml => {
+                out.push_str(&format!("# {}\n", HEADER));
+                for (key, path) in vars {
+                    out.push_str(&format!("{} = {}\n", key, toml::Value::from(absolute(path))));
+                }
+            }
+        }
+        out
+    }
+}
+
+fn escape_double(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('$', "\\$").replace('`', "\\`")
+}
@@ -0,0 +1,151 @@
+// 统一的错误类型：按类别区分退出码，消息按界面语言输出并附带出错的路径
+use std::fmt;
+use std::io;
+use std::path::{ Path, PathBuf };
+
+use crate::{ Language, MsgKey };
+
+pub type Result<T> = std::result::Result<T, YuuskelError>;
+
+#[derive(Debug)]
+pub enum YuuskelError {
+    // 读写文件或目录失败
+    Filesystem {
+        path: PathBuf,
+        source: io::Error,
+    },
+    // 没有读写权限
+    Permission {
+        path: PathBuf,
+        source: io::Error,
+    },
+    // git 命令执行失败（command 不含 git 本身，如 "init"）
+    Git {
+        command: String,
+        path: PathBuf,
+        detail: String,
+    },
+    // 无法运行 git（通常是未安装）
+    GitUnavailable(io::Error),
+    TemplateNotFound {
+        name: String,
+        known: String,
+    },
+    // 模板描述文件或模板文件有误
+    Template {
+        name: String,
+        detail: String,
+    },
+    // 用户配置或 yuuskel.toml 有误
+    Config {
+        path: PathBuf,
+        detail: String,
+    },
+    // 输入不合法（消息已按界面语言生成）
+    Validation(String),
+    // 无法交互时缺少某个问题的答案（参数名，如 "--prefix/--no-prefix"）
+    MissingAnswer(String),
+    // 预先给出的答案不在可选项中
+    InvalidAnswer {
+        question: String,
+        answer: String,
+        expected: String,
+    },
+    // 交互式提示读写终端失败
+    Prompt(io::Error),
+    // 执行计划时收到 Ctrl-C
+    Interrupted,
+}
+
+impl YuuskelError {
+    // 文件系统错误：权限不足单独归类
+    pub fn fs(path: impl AsRef<Path>, source: io::Error) -> Self {
+        let path = path.as_ref().to_path_buf();
+        if source.kind() == io::ErrorKind::PermissionDenied {
+            YuuskelError::Permission { path, source }
+        } else {
+            YuuskelError::Filesystem { path, source }
+        }
+    }
+
+    pub fn template(name: impl Into<String>, detail: impl fmt::Display) -> Self {
+        YuuskelError::Template { name: name.into(), detail: detail.to_string() }
+    }
+
+    pub fn config(path: impl AsRef<Path>, detail: impl fmt::Display) -> Self {
+        YuuskelError::Config { path: path.as_ref().to_path_buf(), detail: detail.to_string() }
+    }
+
+    // 进程退出码（1 保留给 check 未通过）
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            YuuskelError::Validation(_) |
+            YuuskelError::MissingAnswer(_) |
+            YuuskelError::InvalidAnswer { .. } => 2,
+            YuuskelError::Config { .. } => 3,
+            YuuskelError::TemplateNotFound { .. } | YuuskelError::Template { .. } => 4,
+            YuuskelError::Filesystem { .. } => 5,
+            YuuskelError::Permission { .. } => 6,
+            YuuskelError::Git { .. } | YuuskelError::GitUnavailable(_) => 7,
+            YuuskelError::Prompt(_) => 8,
+            // 与 shell 对 SIGINT 的约定一致
+            YuuskelError::Interrupted => 130,
+        }
+    }
+
+    pub fn message(&self, lang: Language) -> String {
+        let fill = |key: MsgKey, args: &[&str]| {
+            args.iter().fold(lang.msg(key).to_string(), |msg, arg| msg.replacen("{}", arg, 1))
+        };
+        match self {
+            YuuskelError::Filesystem { path, source } =>
+                fill(MsgKey::ErrFilesystem, &[&path.display().to_string(), &source.to_string()]),
+            YuuskelError::Permission { path, .. } =>
+                fill(MsgKey::ErrPermission, &[&path.display().to_string()]),
+            YuuskelError::Git { command, path, detail } =>
+                fill(MsgKey::ErrGit, &[command, &path.display().to_string(), detail]),
+            YuuskelError::GitUnavailable(source) =>
+                fill(MsgKey::ErrGitUnavailable, &[&source.to_string()]),
+            YuuskelError::TemplateNotFound { name, known } =>
+                fill(MsgKey::ErrTemplateNotFound, &[name, known]),
+            YuuskelError::Template { name, detail } => fill(MsgKey::ErrTemplate, &[name, detail]),
+            YuuskelError::Config { path, detail } =>
+                fill(MsgKey::ErrConfig, &[&path.display().to_string(), detail]),
+            YuuskelError::Validation(message) => message.clone(),
+            YuuskelError::MissingAnswer(flag) => fill(MsgKey::MissingAnswer, &[flag]),
+            YuuskelError::InvalidAnswer { question, answer, expected } =>
+                fill(MsgKey::ErrInvalidAnswer, &[question, answer, expected]),
+            YuuskelError::Prompt(source) => fill(MsgKey::ErrPrompt, &[&source.to_string()]),
+            YuuskelError::Interrupted => lang.msg(MsgKey::ErrInterrupted).to_string(),
+        }
+    }
+}
+
+// 不带语言的场景（如测试断言）使用英文
+impl fmt::Display for YuuskelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message(Language::En))
+    }
+}
+
+impl std::error::Error for YuuskelError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            YuuskelError::Filesystem { source, .. }
+            | YuuskelError::Permission { source, .. }
+            | YuuskelError::GitUnavailable(source)
+            | YuuskelError::Prompt(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+// 命令行界面的提问出错（只在启用 cli 特性时需要）
+#[cfg(feature = "cli")]
+impl From<dialoguer::Error> for YuuskelError {
+    fn from(e: dialoguer::Error) -> Self {
+        match e {
+            dialoguer::Error::IO(e) => YuuskelError::Prompt(e),
+        }
+    }
+}
@@ -0,0 +1,357 @@
+// 初始化与升级共用的规划步骤（只读取现状，不写磁盘）
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use serde::{ Deserialize, Serialize };
+
+use crate::dotenv::Document;
+use crate::envfiles::EnvFormat;
+use crate::error::Result;
+use crate::gitignore;
+use crate::helpers::Helper;
+use crate::i18n;
+use crate::license::License;
+use crate::plan::{ Action, EnvChange, FileWrite, Plan };
+use crate::stack::{ self, Stack };
+use crate::template::{ self, Context, EnvVar, Template };
+
+// 项目根目录的规范化绝对路径（统一使用 / 分隔）；目录尚不存在时规范化最近的已存在上级
+pub fn project_root(target_dir: &Path) -> String {
+    let mut existing = target_dir.to_path_buf();
+    let mut rest = Vec::new();
+    while !existing.exists() {
+        match (existing.file_name().map(|n| n.to_os_string()), existing.parent()) {
+            (Some(name), Some(parent)) => {
+                rest.push(name);
+                existing = parent.to_path_buf();
+            }
+            _ => {
+                break;
+            }
+        }
+    }
+    let mut abs_path: PathBuf = existing.canonicalize().unwrap_or(existing);
+    abs_path.extend(rest.iter().rev());
+    abs_path.to_string_lossy().replace('\\', "/")
+}
+
+// .env 中路径的写法
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum PathStyle {
+    // PROJECT_ROOT="/abs/proj"，OUTPUT_DIR="/abs/proj/output"
+    #[default]
+    Absolute,
+    // PROJECT_ROOT="."，OUTPUT_DIR="output"（相对项目根目录，移动项目后仍有效）
+    Relative,
+    // PROJECT_ROOT="/abs/proj"，OUTPUT_DIR="${PROJECT_ROOT}/output"
+    Interpolated,
+}
+
+impl PathStyle {
+    fn root(self, abs_str: &str) -> String {
+        match self {
+            PathStyle::Relative => ".".to_string(),
+            PathStyle::Absolute | PathStyle::Interpolated => abs_str.to_string(),
+        }
+    }
+
+    fn dir(self, abs_str: &str, path: &str) -> String {
+        match self {
+            PathStyle::Absolute => format!("{}/{}", abs_str, path),
+            PathStyle::Relative => path.to_string(),
+            PathStyle::Interpolated => format!("${{PROJECT_ROOT}}/{}", path),
+        }
+    }
+}
+
+// 缺失的目录
+pub fn plan_dirs(plan: &mut Plan, dirs: &[String]) {
+    for d in dirs {
+        if !plan.root.join(d).exists() {
+            plan.push(Action::CreateDir(d.clone()));
+        }
+    }
+}
+
+// 每个目录中的 .gitkeep
+pub fn gitkeep_files(dirs: &[String]) -> Vec<String> {
+    dirs.iter()
+        .map(|d| format!("{}/.gitkeep", d))
+        .collect()
+}
+
+// 占位文件：让空目录与被忽略的数据目录在克隆后仍然存在；已存在则不做任何事
+pub fn plan_placeholders(plan: &mut Plan, keep: &[String]) {
+    for path in keep {
+        if !plan.root.join(path).exists() {
+            plan.push(
+                Action::Write(FileWrite {
+                    path: path.clone(),
+                    content: String::new(),
+                    existed: false,
+                })
+            );
+        }
+    }
+}
+
+const DIR_README: &[(&str, &str)] = &[
+    ("en", include_str!("templates/dir_readme.en.md")),
+    ("zh", include_str!("templates/dir_readme.zh.md")),
+    ("zh-Hant", include_str!("templates/dir_readme.zh-Hant.md")),
+    ("ja", include_str!("templates/dir_readme.ja.md")),
+];
+
+// 有说明的目录中写入 README.md（已存在则不做任何事），返回这些 README 的路径
+pub fn plan_dir_readmes(plan: &mut Plan, lang: &str, ctx: &Context) -> Result<Vec<String>> {
+    let stub = i18n::localized(lang, DIR_README);
+    let mut paths = Vec::new();
+    for dir in ctx.dirs.iter().filter(|d| !d.description.is_empty()) {
+        let path = format!("{}/README.md", dir.path);
+        if !plan.root.join(&path).exists() {
+            // 在完整上下文之上提供当前目录的 path 与 description
+            let dir_ctx = minijinja::context! {
+                path => &dir.path,
+                description => &dir.description,
+                ..minijinja::Value::from_serialize(ctx)
+            };
+            plan.push(
+                Action::Write(FileWrite {
+                    path: path.clone(),
+                    content: template::render(&path, stub, dir_ctx)?,
+                    existed: false,
+                })
+            );
+        }
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+// managed 变量（PROJECT_ROOT + 所有 _DIR）：(键, 相对项目根目录的路径)，PROJECT_ROOT 对应 None
+fn managed_vars(prefix: &str, env_vars: &[EnvVar]) -> Vec<(String, Option<String>)> {
+    std::iter
+        ::once(("PROJECT_ROOT".to_string(), None))
+        .chain(env_vars.iter().map(|v| (format!("{}{}", prefix, v.key), Some(v.path.clone()))))
+        .collect()
+}
+
+// 安全增量更新 .env（保留用户自定义内容）
+pub fn plan_env(
+    plan: &mut Plan,
+    prefix: &str,
+    env_vars: &[EnvVar],
+    abs_str: &str,
+    style: PathStyle
+) {
+    let env_path = plan.root.join(".env");
+    let existed = env_path.exists();
+
+    // 读取现有内容（如果存在），managed 变量原位更新，其余内容原样保留
+    let existing_content = fs::read_to_string(&env_path).unwrap_or_default();
+    let mut doc = Document::parse(&existing_content);
+
+    let mut changes = Vec::new();
+    for (key, path) in managed_vars(prefix, env_vars) {
+        let value = match path {
+            Some(path) => style.dir(abs_str, &path),
+            None => style.root(abs_str),
+        };
+        let old = doc.get(&key).map(str::to_string);
+        if doc.set(&key, &value) {
+            changes.push(EnvChange { key, old, new: value });
+        }
+    }
+
+    plan.push(Action::UpdateEnv {
+        file: FileWrite {
+            path: ".env".to_string(),
+            content: doc.to_string(),
+            existed,
+        },
+        changes,
+    });
+}
+
+// 其他环境文件（env.sh、.envrc 等）：内容与 managed 变量不一致时整体重写
+pub fn plan_env_formats(
+    plan: &mut Plan,
+    formats: &[EnvFormat],
+    prefix: &str,
+    env_vars: &[EnvVar],
+    abs_str: &str,
+    style: PathStyle
+) {
+    let vars = managed_vars(prefix, env_vars);
+    for format in formats {
+        let path = format.file_name();
+        let content = format.render(&vars, abs_str, style);
+        match fs::read_to_string(plan.root.join(path)) {
+            Ok(existing) if existing == content => plan.push(Action::Skip(path.to_string())),
+            existing => {
+                plan.push(
+                    Action::Write(FileWrite {
+                        path: path.to_string(),
+                        content,
+                        existed: existing.is_ok(),
+                    })
+                );
+            }
+        }
+    }
+}
+
+// 路径辅助模块：与模板文件一样，不存在则写入，已存在则跳过（用户可能已修改）
+pub fn plan_helpers(plan: &mut Plan, helpers: &[Helper], ctx: &Context) -> Result<()> {
+    for helper in helpers {
+        let path = helper.file_name();
+        if plan.root.join(path).exists() {
+            plan.push(Action::Skip(path.to_string()));
+        } else {
+            plan.push(
+                Action::Write(FileWrite {
+                    path: path.to_string(),
+                    content: helper.render(ctx)?,
+                    existed: false,
+                })
+            );
+        }
+    }
+    Ok(())
+}
+
+// 技术栈预设：清单不存在则写入，USAGE.md 中缺少对应章节时追加到末尾
+// （.gitignore 片段由 plan_gitignore 统一合并）
+pub fn plan_stack(plan: &mut Plan, stack: Stack, lang: &str, ctx: &Context) -> Result<()> {
+    for (path, content) in stack.files() {
+        if plan.root.join(path).exists() {
+            plan.push(Action::Skip(path.to_string()));
+        } else {
+            plan.push(
+                Action::Write(FileWrite {
+                    path: path.to_string(),
+                    content: template::render(path, content, ctx)?,
+                    existed: false,
+                })
+            );
+        }
+    }
+
+    // 以章节的首行判断是否已追加过
+    let section = template::render("USAGE.md", stack.usage(lang), ctx)?;
+    let heading = section.lines().next().unwrap_or_default();
+    plan_merge(plan, "USAGE.md", |content| {
+        if content.lines().any(|line| line.trim_end() == heading) {
+            return content.to_string();
+        }
+        let mut out = content.to_string();
+        if !out.is_empty() {
+            if !out.ends_with('\n') {
+                out.push('\n');
+            }
+            out.push('\n');
+        }
+        out.push_str(&section);
+        out
+    });
+    Ok(())
+}
+
+// .gitignore：模板与技术栈的片段组合为受管区块，合并进已有文件（区块外的用户规则不变）
+pub fn plan_gitignore(plan: &mut Plan, template: &Template, stack: Option<Stack>, keep: &[String]) {
+    let mut names: Vec<&str> = template.gitignore.iter().map(String::as_str).collect();
+    if let Some(stack) = stack {
+        names.extend(stack.gitignore());
+    }
+    let data_dirs: Vec<String> = template.dirs
+        .iter()
+        .filter(|d| d.ignore)
+        .map(|d| d.path.clone())
+        .collect();
+    let block = gitignore::compose(&names, &data_dirs, keep);
+    plan_merge(plan, ".gitignore", |content| gitignore::merge(content, &block));
+}
+
+// 修改文件内容：文件在本次计划中新建时直接修改计划中的内容；
+// 否则读取磁盘上的文件（不存在视为空），有变化时把原本的跳过改为写入
+fn plan_merge(plan: &mut Plan, path: &str, update: impl FnOnce(&str) -> String) {
+    for action in &mut plan.actions {
+        if let Action::Write(file) = action && file.path == path {
+            file.content = update(&file.content);
+            return;
+        }
+    }
+
+    let existing = fs::read_to_string(plan.root.join(path));
+    let current = existing.as_deref().unwrap_or_default();
+    let content = update(current);
+    let skip = plan.actions.iter().position(|a| matches!(a, Action::Skip(p) if p == path));
+    let action = if existing.is_ok() && content == current {
+        Action::Skip(path.to_string())
+    } else {
+        Action::Write(FileWrite { path: path.to_string(), content, existed: existing.is_ok() })
+    };
+    match skip {
+        Some(i) => {
+            plan.actions[i] = action;
+        }
+        None => plan.push(action),
+    }
+}
+
+// 模板文件中可用的内置变量
+pub fn base_vars(abs_str: &str, prefix: &str) -> BTreeMap<String, String> {
+    let project_name = abs_str.rsplit('/').next().unwrap_or_default();
+    let mut vars = BTreeMap::new();
+    vars.insert("project_name".to_string(), project_name.to_string());
+    vars.insert("project_root".to_string(), abs_str.to_string());
+    vars.insert("prefix".to_string(), prefix.trim_end_matches('_').to_string());
+    vars.insert("output_dir".to_string(), format!("{}/output", abs_str));
+    vars.insert("package_name".to_string(), stack::package_name(project_name));
+    vars.insert("date".to_string(), chrono::Local::now().format("%Y-%m-%d").to_string());
+    vars.insert("year".to_string(), chrono::Local::now().format("%Y").to_string());
+    vars
+}
+
+// 由回答决定的模板变量：语言、许可证、版权持有人与技术栈（未选择时为空字符串）
+pub fn answer_vars(
+    vars: &mut BTreeMap<String, String>,
+    lang: &str,
+    license: Option<&License>,
+    holder: Option<&str>,
+    stack: Option<Stack>
+) {
+    vars.insert("language".to_string(), lang.to_string());
+    vars.insert("license".to_string(), license.map(|l| l.spdx).unwrap_or_default().to_string());
+    vars.insert("license_name".to_string(), license.map(|l| l.label).unwrap_or_default().to_string());
+    vars.insert("holder".to_string(), holder.unwrap_or_default().to_string());
+    vars.insert("stack".to_string(), stack.map(Stack::name).unwrap_or_default().to_string());
+    vars.insert("stack_label".to_string(), stack.map(Stack::label).unwrap_or_default().to_string());
+}
+
+// 模板文件：不存在则写入，已存在则跳过
+pub fn plan_files(plan: &mut Plan, template: &Template, lang: &str, ctx: &Context) -> Result<()> {
+    for file in &template.files {
+        if plan.root.join(&file.path).exists() {
+            plan.push(Action::Skip(file.path.clone()));
+            continue;
+        }
+        let content = template.read_file(file, lang)?;
+        let content = if file.render {
+            template::render(&file.path, &content, ctx)?
+        } else {
+            content
+        };
+        plan.push(
+            Action::Write(FileWrite {
+                path: file.path.clone(),
+                content,
+                existed: false,
+            })
+        );
+    }
+    Ok(())
+}
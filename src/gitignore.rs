@@ -0,0 +1,171 @@
+// .gitignore 片段组合：yuuskel 的规则放在受管区块内，区块外的用户规则保持不变
+const BEGIN: &str = "# >>> yuuskel (managed block: regenerated on every run, add your own rules outside) >>>";
+const END: &str = "# <<< yuuskel <<<";
+
+// (片段名, 内容)
+const FRAGMENTS: &[(&str, &str)] = &[
+    ("base", include_str!("templates/gitignore/base")),
+    ("python", include_str!("templates/gitignore/python")),
+    ("jupyter", include_str!("templates/gitignore/jupyter")),
+    ("rust", include_str!("templates/gitignore/rust")),
+    ("julia", include_str!("templates/gitignore/julia")),
+    ("r", include_str!("templates/gitignore/r")),
+    ("node", include_str!("templates/gitignore/node")),
+    ("os", include_str!("templates/gitignore/os")),
+    ("editor", include_str!("templates/gitignore/editor")),
+];
+
+// base 中数据目录的一节；模板中没有需要忽略的目录时整节省略
+const DATA_SECTION: &str = "# Inputs & outputs (often large or sensitive)\n{{data_dirs}}\n\n";
+
+pub fn is_fragment(name: &str) -> bool {
+    FRAGMENTS.iter().any(|(n, _)| *n == name)
+}
+
+pub fn fragment_names() -> Vec<&'static str> {
+    FRAGMENTS.iter()
+        .map(|(n, _)| *n)
+        .collect()
+}
+
+// 按给定顺序组合片段（base 总在最前，重复的只保留一次），生成完整的受管区块；
+// data_dirs 为内容不纳入版本控制的目录（模板中 ignore = true 的目录），
+// keep 为需要保留在版本控制中的占位文件（如 output/.gitkeep）
+pub fn compose(names: &[&str], data_dirs: &[String], keep: &[String]) -> String {
+    let mut selected = vec!["base"];
+    for name in names {
+        if !selected.contains(name) {
+            selected.push(name);
+        }
+    }
+    let rules = data_rules(data_dirs, keep);
+    let body = selected
+        .iter()
+        .filter_map(|name| FRAGMENTS.iter().find(|(n, _)| n == name))
+        .map(|(_, content)| content.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let body = if rules.is_empty() {
+        body.replace(DATA_SECTION, "")
+    } else {
+        body.replace("{{data_dirs}}", &rules)
+    };
+    format!("{}\n{}\n{}\n", BEGIN, body, END)
+}
+
+// 数据目录的忽略规则：没有占位文件时忽略整个目录；
+// 有占位文件时改为忽略目录内容，再逐级放行占位文件（output/* + !output/.gitkeep）
+fn data_rules(data_dirs: &[String], keep: &[String]) -> String {
+    let mut lines = Vec::new();
+    for dir in data_dirs {
+        let prefix = format!("{}/", dir);
+        let kept: Vec<&str> = keep
+            .iter()
+            .filter_map(|k| k.strip_prefix(&prefix))
+            .collect();
+        if kept.is_empty() {
+            lines.push(prefix);
+            continue;
+        }
+        lines.push(format!("{}*", prefix));
+        let mut opened: Vec<String> = Vec::new();
+        for file in kept {
+            // 中间的子目录需要先放行目录本身，再忽略其内容
+            let parents = file.rsplit_once('/').map(|(parent, _)| parent.split('/'));
+            let mut current = dir.to_string();
+            for part in parents.into_iter().flatten() {
+                current = format!("{}/{}", current, part);
+                if !opened.contains(&current) {
+                    lines.push(format!("!{}/", current));
+                    lines.push(format!("{}/*", current));
+                    opened.push(current.clone());
+                }
+            }
+            lines.push(format!("!{}{}", prefix, file));
+        }
+    }
+    lines.join("\n")
+}
+
+// 将受管区块合并进已有内容：已有区块则原位替换，否则追加到末尾
+pub fn merge(existing: &str, block: &str) -> String {
+    let begin = existing.lines().position(|l| l.trim_end().starts_with("# >>> yuuskel"));
+    let end = existing.lines().position(|l| l.trim_end() == END);
+    if let (Some(begin), Some(end)) = (begin, end) && begin < end {
+        let lines: Vec<&str> = existing.split_inclusive('\n').collect();
+        let mut out: String = lines[..begin].concat();
+        out.push_str(block);
+        out.push_str(&lines[end + 1..].concat());
+        return out;
+    }
+    let mut out = existing.to_string();
+    if !out.is_empty() {
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    out.push_str(block);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dirs(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn compose_keeps_base_rules() {
+        let block = compose(&["os"], &dirs(&["input", "output"]), &[]);
+        let lines: Vec<&str> = block.lines().collect();
+        assert_eq!(lines.first(), Some(&BEGIN));
+        assert_eq!(lines.last(), Some(&END));
+        for rule in [".env", "input/", "output/", "__pycache__/", "*.pyc", "build/", "dist/", ".DS_Store"] {
+            assert!(lines.contains(&rule), "{}", block);
+        }
+        // 未列出的片段不包含；base 在最前
+        assert!(!block.contains(".vscode/"), "{}", block);
+        assert!(block.find(".env").unwrap() < block.find(".DS_Store").unwrap());
+    }
+
+    #[test]
+    fn compose_fragments_once_in_order() {
+        let block = compose(&["editor", "base", "python", "editor"], &[], &[]);
+        assert_eq!(block.matches("# Editors").count(), 1);
+        assert_eq!(block.matches("# Local config").count(), 1);
+        assert!(block.find("# Editors").unwrap() < block.find("# Python").unwrap());
+        // 没有数据目录时省略整节
+        assert!(!block.contains("Inputs & outputs") && !block.contains("{{data_dirs}}"), "{}", block);
+    }
+
+    #[test]
+    fn data_rules_keep_placeholders() {
+        let keep = dirs(&["output/.gitkeep", "output/figs/.gitkeep", "src/.gitkeep"]);
+        assert_eq!(
+            data_rules(&dirs(&["input", "output"]), &keep),
+            "input/\noutput/*\n!output/.gitkeep\n!output/figs/\noutput/figs/*\n!output/figs/.gitkeep"
+        );
+    }
+
+    #[test]
+    fn merge_replaces_block_in_place() {
+        let old = compose(&[], &dirs(&["input"]), &[]);
+        let new = compose(&["os"], &dirs(&["input"]), &[]);
+        let existing = format!("# mine\nsecret.txt\n\n{}\n!input/keep.csv\n", old);
+
+        let merged = merge(&existing, &new);
+        assert_eq!(merged, format!("# mine\nsecret.txt\n\n{}\n!input/keep.csv\n", new));
+        assert_eq!(merge(&merged, &new), merged);
+    }
+
+    #[test]
+    fn merge_appends_block() {
+        let block = compose(&[], &[], &[]);
+        assert_eq!(merge("", &block), block);
+        assert_eq!(merge("*.bak", &block), format!("*.bak\n\n{}", block));
+        assert_eq!(merge("*.bak\n", &block), format!("*.bak\n\n{}", block));
+    }
+}
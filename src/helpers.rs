@@ -0,0 +1,44 @@
+// 各语言的路径辅助模块：按 env 表渲染（模板见 templates/helpers/），优先读取环境变量与 .env，缺失时回退到相对项目根目录的默认路径
+
+use serde::{ Deserialize, Serialize };
+
+use crate::error::Result;
+use crate::template::{ self, Context };
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum Helper {
+    // src/paths.py：pathlib.Path 常量
+    Python,
+    // src/paths.rs：返回 PathBuf 的函数
+    Rust,
+    // src/paths.jl：Paths 模块中的常量
+    Julia,
+    // src/paths.R：source() 后可用的变量
+    R,
+}
+
+impl Helper {
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Helper::Python => "src/paths.py",
+            Helper::Rust => "src/paths.rs",
+            Helper::Julia => "src/paths.jl",
+            Helper::R => "src/paths.R",
+        }
+    }
+
+    fn skeleton(self) -> &'static str {
+        match self {
+            Helper::Python => include_str!("templates/helpers/paths.py"),
+            Helper::Rust => include_str!("templates/helpers/paths.rs"),
+            Helper::Julia => include_str!("templates/helpers/paths.jl"),
+            Helper::R => include_str!("templates/helpers/paths.R"),
+        }
+    }
+
+    pub fn render(self, ctx: &Context) -> Result<String> {
+        template::render(self.file_name(), self.skeleton(), ctx)
+    }
+}
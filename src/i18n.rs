@@ -0,0 +1,277 @@
+// 界面文本：每种语言一个消息目录（locales/*.toml），缺失的键回退到英文
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+#[derive(Clone, Copy, PartialEq, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Language {
+    #[serde(rename = "en")]
+    En,
+    #[serde(rename = "zh")]
+    Zh,
+    // 繁体中文
+    #[cfg_attr(feature = "cli", value(name = "zh-Hant", alias = "zh-hant"))]
+    #[serde(rename = "zh-Hant", alias = "zh-hant")]
+    ZhHant,
+    #[serde(rename = "ja")]
+    Ja,
+}
+
+struct Locale {
+    code: &'static str,
+    name: &'static str,
+    catalog: &'static str,
+}
+
+// 与 Language 的变体顺序一致
+const LOCALES: [Locale; 4] = [
+    Locale { code: "en", name: "English", catalog: include_str!("locales/en.toml") },
+    Locale { code: "zh", name: "简体中文", catalog: include_str!("locales/zh.toml") },
+    Locale { code: "zh-Hant", name: "繁體中文", catalog: include_str!("locales/zh-Hant.toml") },
+    Locale { code: "ja", name: "日本語", catalog: include_str!("locales/ja.toml") },
+];
+
+type Catalog = BTreeMap<MsgKey, String>;
+
+impl Language {
+    pub fn all() -> Vec<(&'static str, Self)> {
+        [Language::En, Language::Zh, Language::ZhHant, Language::Ja]
+            .into_iter()
+            .map(|lang| (lang.locale().name, lang))
+            .collect()
+    }
+
+    fn locale(self) -> &'static Locale {
+        &LOCALES[self as usize]
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.locale().code
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Language::all()
+            .into_iter()
+            .map(|(_, lang)| lang)
+            .find(|lang| lang.code() == code)
+    }
+
+    // 按 POSIX 的优先级读取区域设置（LC_ALL → LC_MESSAGES → LANG）；未设置或不支持时返回 None
+    pub fn detect() -> Option<Self> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Language::from_locale(&value))
+    }
+
+    // zh_CN.UTF-8 → 简体中文，zh_TW / zh_HK / zh-Hant → 繁体中文，ja_JP → 日本語
+    fn from_locale(locale: &str) -> Option<Self> {
+        let tag = locale.split(['.', '@']).next()?.to_ascii_lowercase();
+        let mut parts = tag.split(['_', '-']);
+        match parts.next()? {
+            "en" => Some(Language::En),
+            "ja" => Some(Language::Ja),
+            "zh" if parts.any(|p| matches!(p, "hant" | "tw" | "hk" | "mo")) => Some(Language::ZhHant),
+            "zh" => Some(Language::Zh),
+            _ => None,
+        }
+    }
+
+    pub fn msg(&self, key: MsgKey) -> &'static str {
+        self.catalog()
+            .get(&key)
+            .or_else(|| Language::En.catalog().get(&key))
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
+    fn catalog(self) -> &'static Catalog {
+        static CATALOGS: [OnceLock<Catalog>; LOCALES.len()] = [const { OnceLock::new() }; LOCALES.len()];
+        CATALOGS[self as usize].get_or_init(|| {
+            // 目录编译进二进制，格式错误由测试保证不会发生
+            parse(self.locale().catalog).unwrap_or_else(|e| panic!("invalid catalog {}: {}", self.code(), e))
+        })
+    }
+}
+
+// 按语言代码查找本地化内容时的回退顺序：zh-Hant → zh → en，其他语言直接回退到 en
+pub fn fallbacks(code: &str) -> Vec<&str> {
+    let mut chain = vec![code];
+    if let Some((base, _)) = code.split_once('-') {
+        chain.push(base);
+    }
+    if !chain.contains(&"en") {
+        chain.push("en");
+    }
+    chain
+}
+
+// 从 (语言代码, 内容) 中按回退顺序选出一项，都没有时取第一项
+pub fn localized<'a>(code: &str, variants: &[(&str, &'a str)]) -> &'a str {
+    fallbacks(code)
+        .into_iter()
+        .find_map(|lang| variants.iter().find(|(l, _)| *l == lang))
+        .or(variants.first())
+        .map(|(_, content)| *content)
+        .unwrap_or_default()
+}
+
+fn parse(content: &str) -> Result<Catalog, toml::de::Error> {
+    toml::from_str(content)
+}
+
+// 目录中的键为对应变体的 snake_case 形式，如 InitDone → init_done
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Deserialize)]
+#[cfg_attr(test, derive(strum::EnumIter))]
+#[serde(rename_all = "snake_case")]
+pub enum MsgKey {
+    Title,
+    InitModePrompt,
+    NewItemProject,
+    InitInCurrent,
+    ProjectNamePrompt,
+    NameTooLong,
+    InvalidChars,
+    TargetDir,
+    DirExistsPrompt,
+    Cancelled,
+    CreateDir,
+    AddDir,
+    EnvPrefixPrompt,
+    NoPrefix,
+    WithPrefix,
+    PrefixPrompt,
+    UpdateDotEnv,
+    SkipExisting,
+    GitInitPrompt,
+    Yes,
+    No,
+    GitInitialized,
+    GitConfigMissing,
+    InitialCommitPrompt,
+    GitAddHint,
+    CommitSuccess,
+    LicensePrompt,
+    SkipLicense,
+    Proprietary,
+    IncrementalUpdateDone,
+    InitDone,
+    GuidePath,
+    ReadmePath,
+    EnvPath,
+    PrefixAdded,
+    PrefixLocked,
+    PathOutsideProject,
+    InvalidPrefix,
+    DotenvTip,
+    MissingAnswer,
+    AnswersSaved,
+    DryRunHeader,
+    DryRunNoChanges,
+    HolderPrompt,
+    TemplateInfo,
+    TemplateListBuiltin,
+    TemplateListCached,
+    TemplateListEmpty,
+    TemplateFetched,
+    TemplateUpdated,
+    TemplateUpToDate,
+    TemplateRemoved,
+    TemplateNotCached,
+    StackPrompt,
+    NoStack,
+    NotYuuskelProject,
+    UpgradeTitle,
+    UpgradeVersion,
+    UpgradeDone,
+    UpToDate,
+    CheckTitle,
+    CheckDirMissing,
+    CheckEnvFileMissing,
+    CheckEnvKeyMissing,
+    CheckEnvOutside,
+    CheckRootMismatch,
+    CheckPrefixMismatch,
+    CheckGitignoreEnv,
+    CheckGitignoreMissing,
+    CheckPassed,
+    CheckFailed,
+    RelocateMoved,
+    RelocateInSync,
+    RelocateDone,
+    InitFailed,
+    UpgradeFailed,
+    ErrFilesystem,
+    ErrPermission,
+    ErrGit,
+    ErrGitUnavailable,
+    ErrTemplateNotFound,
+    ErrTemplate,
+    ErrConfig,
+    ErrPrompt,
+    ErrInterrupted,
+    ErrInvalidAnswer,
+    RollbackHeader,
+    RollbackRemoved,
+    RollbackRestored,
+    RollbackFailed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn every_locale_covers_every_key() {
+        for (_, lang) in Language::all() {
+            let catalog = parse(lang.locale().catalog).unwrap_or_else(|e| panic!("{}: {}", lang.code(), e));
+            let missing: Vec<_> = MsgKey::iter()
+                .filter(|k| !catalog.contains_key(k))
+                .collect();
+            assert!(missing.is_empty(), "{} is missing {:?}", lang.code(), missing);
+        }
+    }
+
+    #[test]
+    fn placeholders_match_english() {
+        for (_, lang) in Language::all() {
+            for key in MsgKey::iter() {
+                assert_eq!(
+                    lang.msg(key).matches("{}").count(),
+                    Language::En.msg(key).matches("{}").count(),
+                    "{} {:?}",
+                    lang.code(),
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn locale_detection() {
+        assert!(Language::from_locale("zh_CN.UTF-8") == Some(Language::Zh));
+        assert!(Language::from_locale("zh_TW.UTF-8") == Some(Language::ZhHant));
+        assert!(Language::from_locale("zh-Hant-HK") == Some(Language::ZhHant));
+        assert!(Language::from_locale("ja_JP.eucJP") == Some(Language::Ja));
+        assert!(Language::from_locale("en_US.UTF-8@euro") == Some(Language::En));
+        assert!(Language::from_locale("C.UTF-8").is_none());
+        assert!(Language::from_locale("POSIX").is_none());
+        assert!(Language::from_locale("fr_FR.UTF-8").is_none());
+    }
+
+    #[test]
+    fn localized_fallback() {
+        assert_eq!(fallbacks("zh-Hant"), ["zh-Hant", "zh", "en"]);
+        assert_eq!(fallbacks("ja"), ["ja", "en"]);
+        assert_eq!(fallbacks("en"), ["en"]);
+        let variants = [("en", "English"), ("zh", "简体")];
+        assert_eq!(localized("zh-Hant", &variants), "简体");
+        assert_eq!(localized("ja", &variants), "English");
+        assert_eq!(localized("ja", &[("zh", "简体")]), "简体");
+    }
+}
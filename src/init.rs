@@ -0,0 +1,484 @@
+// 交互式初始化：先收集答案，再生成计划，最后执行（或 --dry-run 只打印）
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::path::{ Component, Path, PathBuf };
+
+use colored::*;
+
+use crate::answers::to_answer;
+use crate::cli::InitArgs;
+use crate::config::Config;
+use crate::report;
+use crate::git_config;
+use crate::prompt::{ Prompter, Question };
+use yuuskel::error::{ Result, YuuskelError };
+use yuuskel::license;
+use yuuskel::metadata::Metadata;
+use yuuskel::plan::Interrupt;
+use yuuskel::remote;
+use yuuskel::skeleton::{ self, validate_prefix, validate_project_name, Skeleton };
+use yuuskel::stack::Stack;
+use yuuskel::template::Template;
+use yuuskel::{ Language, MsgKey };
+
+// 使用的模板：--template → 已有项目记录的模板（固定在记录的提交）→ 用户配置 → default
+fn load_template(args: &InitArgs, config: &Config, recorded: Option<&Metadata>) -> Result<(String, Template)> {
+    if let Some(name) = &args.template {
+        return Ok((name.clone(), Template::load(name)?));
+    }
+    if let Some(metadata) = recorded {
+        let template = Template::load_pinned(&metadata.template, metadata.template_commit.as_deref())?;
+        return Ok((metadata.template.clone(), template));
+    }
+    let name = config.template.as_deref().unwrap_or("default");
+    Ok((name.to_string(), Template::load(name)?))
+}
+
+pub fn run(
+    lang: Language,
+    args: &InitArgs,
+    config: &Config,
+    prompter: &mut dyn Prompter,
+    interrupt: &Interrupt
+) -> Result<()> {
+    println!("{}", lang.msg(MsgKey::Title).cyan().bold());
+
+    // 选择模式（--here / --name 可跳过）
+    let mode = Question { key: "mode", flag: "--here/--name", message: lang.msg(MsgKey::InitModePrompt) };
+    let modes = [("new", lang.msg(MsgKey::NewItemProject)), ("here", lang.msg(MsgKey::InitInCurrent))];
+    let here = prompter.select(&mode, &modes, 0)? == 1;
+
+    let target_dir = if here {
+        std::env::current_dir().map_err(|e| YuuskelError::fs(".", e))?
+    } else {
+        let question = Question { key: "name", flag: "--name", message: lang.msg(MsgKey::ProjectNamePrompt) };
+        // 也可以给出路径（如 ../exp/run1），只校验最后一级的文件夹名
+        let validate = |input: &str| {
+            let name = match Path::new(input).components().next_back() {
+                Some(Component::Normal(name)) => name.to_string_lossy(),
+                _ => Cow::Borrowed(""),
+            };
+            if name.len() > 100 {
+                Err(lang.msg(MsgKey::NameTooLong).to_string())
+            } else if validate_project_name(&name) {
+                Ok(())
+            } else {
+                Err(lang.msg(MsgKey::InvalidChars).to_string())
+            }
+        };
+        let name = prompter.input(&question, Some("my_project"), &validate)?;
+
+        let path = PathBuf::from(&name);
+        if path.is_absolute() {
+            path
+        } else {
+            std::env::current_dir().map_err(|e| YuuskelError::fs(".", e))?.join(name)
+        }
+    };
+
+    println!("{}{}", lang.msg(MsgKey::TargetDir), target_dir.display().to_string().cyan());
+
+    if target_dir.exists() && !here && !args.yes && !args.dry_run {
+        let question = Question { key: "overwrite", flag: "--yes", message: lang.msg(MsgKey::DirExistsPrompt) };
+        if !prompter.confirm(&question, false)? {
+            println!("{}", lang.msg(MsgKey::Cancelled).red());
+            return Ok(());
+        }
+    }
+
+    let is_existing = target_dir.exists();
+
+    // 已有 yuuskel.toml 时沿用之前的答案作为默认值
+    let recorded = Metadata::load(&target_dir)?;
+
+    // 在任何写入之前加载模板，模板有误时尽早失败
+    let (template_source, template) = load_template(args, config, recorded.as_ref())?;
+    prompter.record("template", &template_source);
+    // git+<URL> 模板附带检出的提交
+    let label = match template.commit() {
+        Some(commit) => format!("{} @ {}", template.name, remote::short(commit)),
+        None => template.name.clone(),
+    };
+    if template.description.is_empty() {
+        println!("{}{}", lang.msg(MsgKey::TemplateInfo), label.cyan());
+    } else {
+        println!("{}{} — {}", lang.msg(MsgKey::TemplateInfo), label.cyan(), template.description.dimmed());
+    }
+
+    let recorded_prefix = recorded.as_ref().and_then(|m| m.prefix.clone());
+
+    // === 询问是否使用环境变量前缀（已有项目沿用记录的前缀，不再询问）===
+    let question = Question {
+        key: "use_prefix",
+        flag: "--prefix/--no-prefix",
+        message: lang.msg(MsgKey::EnvPrefixPrompt),
+    };
+    let choices = [("no", lang.msg(MsgKey::NoPrefix)), ("yes", lang.msg(MsgKey::WithPrefix))];
+    let keep_recorded = recorded.is_some() && !prompter.has_answer("use_prefix");
+    let use_prefix = if keep_recorded {
+        prompter.record("use_prefix", if recorded_prefix.is_some() { "yes" } else { "no" });
+        if let Some(prefix) = &recorded_prefix {
+            prompter.record("prefix", prefix);
+        }
+        recorded_prefix.is_some()
+    } else {
+        prompter.select(&question, &choices, if recorded_prefix.is_some() { 1 } else { 0 })? == 1
+    };
+    let prefix_input = if keep_recorded {
+        recorded_prefix.clone()
+    } else if use_prefix {
+        let folder_name = target_dir.file_name().unwrap_or_default().to_string_lossy();
+        let default_prefix: String = recorded_prefix.clone().unwrap_or_else(|| {
+            let prefix: String = folder_name
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+                .collect();
+            // 以数字开头的文件夹名（如 2024exp）前面补 _
+            if prefix.starts_with(|c: char| c.is_ascii_digit()) { format!("_{}", prefix) } else { prefix }
+        });
+        let question = Question { key: "prefix", flag: "--prefix", message: lang.msg(MsgKey::PrefixPrompt) };
+        // --prefix 给出的答案同样经过校验
+        let validate = |input: &str| {
+            if validate_prefix(input.trim()) {
+                Ok(())
+            } else {
+                Err(lang.msg(MsgKey::InvalidPrefix).replace("{}", &format!("'{}'", input.trim())))
+            }
+        };
+        Some(prompter.input(&question, Some(&default_prefix), &validate)?)
+    } else {
+        None
+    };
+
+    // === 技术栈预设（已记录的作为默认值）===
+    let default_stack = recorded.as_ref().and_then(|m| m.stack).or(config.stack);
+    let question = Question { key: "stack", flag: "--stack/--no-stack", message: lang.msg(MsgKey::StackPrompt) };
+    let choices: Vec<_> = std::iter
+        ::once(("none", lang.msg(MsgKey::NoStack)))
+        .chain(Stack::all().map(|s| (s.name(), s.label())))
+        .collect();
+    let default = default_stack
+        .and_then(|d| Stack::all().iter().position(|s| *s == d))
+        .map_or(0, |i| i + 1);
+    let stack = prompter.select(&question, &choices, default)?.checked_sub(1).map(|i| Stack::all()[i]);
+
+    // === 模板问题（已记录的答案作为默认值）===
+    let mut answers = BTreeMap::new();
+    for prompt in &template.prompts {
+        let recorded_answer = recorded.as_ref().and_then(|m| m.vars.get(&prompt.name));
+        let default_answer = recorded_answer.unwrap_or(&prompt.default);
+        let key = format!("var.{}", prompt.name);
+        let flag = format!("--var {}=…", prompt.name);
+        let question = Question { key: &key, flag: &flag, message: prompt.message.get(lang.code()) };
+        let answer = prompter.input(&question, Some(default_answer), &|_| Ok(()))?;
+        answers.insert(prompt.name.clone(), answer);
+    }
+
+    // 询问是否初始化 Git
+    let default_git = config.git.init.unwrap_or(false);
+    let question = Question { key: "git", flag: "--git/--no-git", message: lang.msg(MsgKey::GitInitPrompt) };
+    let choices = [("yes", lang.msg(MsgKey::Yes)), ("no", lang.msg(MsgKey::No))];
+    let init_git = prompter.select(&question, &choices, if default_git { 0 } else { 1 })? == 0;
+
+    // 检查 git config（初始提交需要 user.name / user.email）
+    let config_dir = if target_dir.exists() {
+        target_dir.clone()
+    } else {
+        std::env::current_dir().map_err(|e| YuuskelError::fs(".", e))?
+    };
+    let git_user = git_config(&config_dir, "user.name");
+    let has_email = git_config(&config_dir, "user.email").is_some();
+
+    // 已有仓库中不做初始提交（见 Skeleton::plan），也就不再询问
+    let mut add_commit = false;
+    if init_git && !target_dir.join(".git").exists() {
+        if git_user.is_none() || !has_email {
+            eprintln!("{}", lang.msg(MsgKey::GitConfigMissing));
+        } else {
+            let default_commit = config.git.commit.unwrap_or(true);
+            let question = Question {
+                key: "commit",
+                flag: "--commit/--no-commit",
+                message: lang.msg(MsgKey::InitialCommitPrompt),
+            };
+            add_commit = prompter.confirm(&question, default_commit)?;
+        }
+    }
+
+    // === 许可证选择（仅新建项目时询问，已有项目沿用记录的许可证；--license 始终生效）===
+    let license = if is_existing && !prompter.has_answer("license") {
+        let recorded_license = recorded.as_ref().and_then(|m| m.license.as_deref()).and_then(license::find);
+        prompter.record("license", recorded_license.map_or("none", |l| l.spdx));
+        recorded_license
+    } else {
+        let default_license = config.license.as_deref().and_then(license::find);
+        let question = Question { key: "license", flag: "--license", message: lang.msg(MsgKey::LicensePrompt) };
+        let choices: Vec<_> = std::iter
+            ::once(("none", lang.msg(MsgKey::SkipLicense)))
+            .chain(
+                license::LICENSES.iter().map(|l| {
+                    if l.spdx == "Proprietary" { (l.spdx, lang.msg(MsgKey::Proprietary)) } else { (l.spdx, l.label) }
+                })
+            )
+            .collect();
+        let default = default_license
+            .and_then(|d| license::LICENSES.iter().position(|l| l.spdx == d.spdx))
+            .map_or(0, |i| i + 1);
+        prompter.select(&question, &choices, default)?.checked_sub(1).and_then(|i| license::LICENSES.get(i))
+    };
+
+    // 版权持有人：--holder → 已记录的 → 配置中的 author.name → git user.name → 询问
+    let known_holder = recorded
+        .as_ref()
+        .and_then(|m| m.holder.clone())
+        .or(config.author.name.clone())
+        .or(git_user);
+    let holder = match (license, known_holder) {
+        (None, _) => None,
+        (Some(_), Some(holder)) if !prompter.has_answer("holder") => {
+            prompter.record("holder", &holder);
+            Some(holder)
+        }
+        (Some(_), _) => {
+            let question = Question { key: "holder", flag: "--holder", message: lang.msg(MsgKey::HolderPrompt) };
+            Some(prompter.input(&question, None, &|_| Ok(()))?)
+        }
+    };
+
+    // 路径辅助模块：命令行 + 配置 + 已记录的
+    let helpers = args.helpers
+        .iter()
+        .chain(&config.helpers)
+        .chain(recorded.iter().flat_map(|m| &m.helpers));
+
+    // 额外的环境文件：命令行 + 配置 + 已记录的
+    let env_formats = args.env_formats
+        .iter()
+        .chain(&config.env_formats)
+        .chain(recorded.iter().flat_map(|m| &m.env_formats));
+    // 占位文件：.gitkeep 与目录说明 README.md
+    let gitkeep = args.gitkeep || config.gitkeep || recorded.as_ref().is_some_and(|m| m.gitkeep);
+    let dir_readmes =
+        args.dir_readmes || config.dir_readmes || recorded.as_ref().is_some_and(|m| m.dir_readmes);
+    let env_paths = args.env_paths
+        .or(recorded.as_ref().map(|m| m.env_paths))
+        .unwrap_or_default();
+
+    // === 生成计划 ===
+    let mut skeleton = Skeleton::new(&target_dir)
+        .template(template)
+        .language(lang)
+        .prefix(prefix_input.as_deref().unwrap_or_default())
+        .env_paths(env_paths)
+        .stack(stack)
+        .gitkeep(gitkeep)
+        .dir_readmes(dir_readmes)
+        .license(license, holder)
+        .author(config.author.name.clone(), config.author.email.clone())
+        .git(init_git, add_commit);
+    // 配置中的额外目录追加在模板目录之后
+    for dir in &config.extra_dirs {
+        skeleton = skeleton.dir(dir);
+    }
+    for (name, value) in answers {
+        skeleton = skeleton.var(name, value);
+    }
+    for format in env_formats {
+        skeleton = skeleton.env_format(*format);
+    }
+    for helper in helpers {
+        skeleton = skeleton.helper(*helper);
+    }
+    let spec = skeleton.build()?;
+
+    // 不是问题的选项也一并记录（--save-answers）
+    let yes_no = |value: bool| if value { "yes" } else { "no" };
+    let join = |values: Vec<String>| values.join(",");
+    prompter.record("gitkeep", yes_no(spec.gitkeep));
+    prompter.record("dir_readmes", yes_no(spec.dir_readmes));
+    prompter.record("env_paths", &to_answer(&spec.env_paths));
+    prompter.record("env_formats", &join(spec.env_formats.iter().map(to_answer).collect()));
+    prompter.record("helpers", &join(spec.helpers.iter().map(to_answer).collect()));
+    let plan = spec.plan()?;
+
+    if args.dry_run {
+        report::print_plan(lang, &plan);
+        return Ok(());
+    }
+
+    skeleton
+        ::apply(&plan, interrupt, |action, outcome| report::print_step(lang, is_existing, action, outcome))
+        .map_err(|rollback| {
+            report::print_rollback(lang, &rollback.steps);
+            rollback.error
+        })?;
+
+    if is_existing {
+        println!("\n{}", lang.msg(MsgKey::IncrementalUpdateDone).green().bold());
+    } else {
+        println!("\n{}", lang.msg(MsgKey::InitDone).green().bold());
+    }
+    // 处理 GuidePath 消息（手动替换两个占位符）
+    let guide_path_msg = lang.msg(MsgKey::GuidePath);
+    let guide_path_output = guide_path_msg
+        .replacen("{}", &target_dir.display().to_string(), 1)
+        .replacen("{}", &"USAGE.md".cyan().to_string(), 1);
+    println!("{}", guide_path_output);
+
+    // 处理 ReadmePath 消息
+    let readme_path_msg = lang.msg(MsgKey::ReadmePath);
+    let readme_path_output = readme_path_msg
+        .replacen("{}", &target_dir.display().to_string(), 1)
+        .replacen("{}", &"README.md".cyan().to_string(), 1);
+    println!("{}", readme_path_output);
+
+    // 处理 EnvPath 消息
+    let env_path_msg = lang.msg(MsgKey::EnvPath);
+    let env_path_output = env_path_msg
+        .replacen("{}", &target_dir.display().to_string(), 1)
+        .replacen("{}", &".env".cyan().to_string(), 1);
+    println!("{}", env_path_output);
+
+    if let Some(prefix) = &spec.prefix {
+        println!("{}{}", lang.msg(MsgKey::PrefixAdded), prefix.yellow().bold());
+    }
+    println!("{}", lang.msg(MsgKey::DotenvTip).dimmed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::prompt::{ Defaults, Preanswered, Scripted };
+
+    const MANIFEST: &str = r#"name = "lab"
+dirs = ["data"]
+env = [{ key = "DATA_DIR", path = "data" }]
+
+[[prompts]]
+name = "pi"
+message = "Principal investigator"
+default = "nobody"
+
+[[files]]
+path = "README.md"
+source = "readme.md"
+"#;
+
+    fn setup() -> (tempfile::TempDir, InitArgs, Config) {
+        let dir = tempfile::tempdir().unwrap();
+        let template = dir.path().join("lab");
+        fs::create_dir_all(template.join("files")).unwrap();
+        fs::write(template.join("template.toml"), MANIFEST).unwrap();
+        fs::write(template.join("files/readme.md"), "# {{ project_name }} ({{ pi }})\n").unwrap();
+        let args = InitArgs { template: Some(template.display().to_string()), ..InitArgs::default() };
+        let mut config = Config::default();
+        config.author.name = Some("Jane Doe".to_string());
+        (dir, args, config)
+    }
+
+    fn read(root: &Path, file: &str) -> String {
+        fs::read_to_string(root.join(file)).unwrap()
+    }
+
+    // 完整的交互式创建：每个问题按顺序出现一次
+    #[test]
+    fn interactive_create() {
+        let (dir, args, config) = setup();
+        let root = dir.path().join("proj");
+        let mut prompter = Scripted::new(&[
+            ("mode", "new"),
+            ("name", &root.display().to_string()),
+            ("use_prefix", "yes"),
+            ("prefix", "lab"),
+            ("stack", "python"),
+            ("var.pi", "Curie"),
+            ("git", "no"),
+            ("license", "MIT"),
+        ]);
+        run(Language::En, &args, &config, &mut prompter, &Interrupt::new()).unwrap();
+        assert!(prompter.is_done());
+
+        assert!(root.join("data").is_dir());
+        assert!(read(&root, ".env").contains("LAB_DATA_DIR="));
+        assert_eq!(read(&root, "README.md"), "# proj (Curie)\n");
+        assert!(read(&root, "LICENSE").contains("Jane Doe"));
+        let metadata = Metadata::load(&root).unwrap().unwrap();
+        assert_eq!(metadata.prefix.as_deref(), Some("LAB"));
+        assert!(metadata.stack == Some(Stack::Python));
+        assert_eq!(metadata.license.as_deref(), Some("MIT"));
+        assert_eq!(metadata.holder.as_deref(), Some("Jane Doe"));
+        assert_eq!(metadata.vars.get("pi").map(String::as_str), Some("Curie"));
+    }
+
+    // 交互输入的前缀同样要符合变量名规则；以数字开头的文件夹名得到以 _ 开头的默认前缀
+    #[test]
+    fn interactive_prefix_is_validated() {
+        let (dir, args, config) = setup();
+        let root = dir.path().join("proj");
+        let mut prompter = Scripted::new(&[
+            ("mode", "new"),
+            ("name", &root.display().to_string()),
+            ("use_prefix", "yes"),
+            ("prefix", "my-lab"),
+        ]);
+        let result = run(Language::En, &args, &config, &mut prompter, &Interrupt::new());
+        assert!(matches!(result, Err(YuuskelError::Validation(_))));
+        assert!(!root.exists());
+
+        let root = dir.path().join("2024exp");
+        let answers = BTreeMap::from([
+            ("mode".to_string(), "new".to_string()),
+            ("name".to_string(), root.display().to_string()),
+            ("use_prefix".to_string(), "yes".to_string()),
+        ]);
+        let mut prompter = Preanswered::new(answers, Defaults);
+        run(Language::En, &args, &config, &mut prompter, &Interrupt::new()).unwrap();
+        assert_eq!(Metadata::load(&root).unwrap().unwrap().prefix.as_deref(), Some("_2024EXP"));
+    }
+
+    // 重新运行：前缀、许可证与持有人沿用记录不再询问，其余问题以记录的答案为默认值，新的答案写回 yuuskel.toml
+    #[test]
+    fn interactive_rerun() {
+        let (dir, args, config) = setup();
+        let root = dir.path().join("proj");
+        let path = root.display().to_string();
+        let mut prompter = Scripted::new(&[
+            ("mode", "new"),
+            ("name", &path),
+            ("use_prefix", "no"),
+            ("stack", "none"),
+            ("var.pi", "Curie"),
+            ("git", "no"),
+            ("license", "MIT"),
+        ]);
+        run(Language::En, &args, &config, &mut prompter, &Interrupt::new()).unwrap();
+        assert!(prompter.is_done());
+
+        let args = InitArgs::default();
+        let mut prompter = Scripted::new(&[
+            ("mode", "new"),
+            ("name", &path),
+            ("overwrite", "yes"),
+            ("stack", "rust"),
+            ("var.pi", "Noether"),
+            ("git", "no"),
+        ]);
+        run(Language::En, &args, &config, &mut prompter, &Interrupt::new()).unwrap();
+        assert!(prompter.is_done());
+
+        // 没有 --template 时使用记录的模板；已有的 README 保持不变
+        assert!(!root.join("input").exists());
+        assert_eq!(read(&root, "README.md"), "# proj (Curie)\n");
+        assert!(root.join("src/Cargo.toml").is_file());
+        let metadata = Metadata::load(&root).unwrap().unwrap();
+        assert_eq!(metadata.prefix, None);
+        assert!(metadata.stack == Some(Stack::Rust));
+        assert_eq!(metadata.license.as_deref(), Some("MIT"));
+        assert_eq!(metadata.holder.as_deref(), Some("Jane Doe"));
+        assert_eq!(metadata.vars.get("pi").map(String::as_str), Some("Noether"));
+    }
+}
@@ -0,0 +1,23 @@
+// yuuskel 的库部分：模板、规划与执行，不做任何终端输入输出；命令行界面见 main.rs
+//
+// 用法：
+//   let spec = Skeleton::new("my_project").prefix("MYPROJ").git(true, true).build()?;
+//   let report = yuuskel::generate(&spec)?;
+pub mod dotenv;
+pub mod envfiles;
+pub mod error;
+pub mod generate;
+pub mod gitignore;
+pub mod helpers;
+pub mod i18n;
+pub mod license;
+pub mod metadata;
+pub mod plan;
+pub mod remote;
+pub mod skeleton;
+pub mod stack;
+pub mod template;
+
+pub use error::{ Result, YuuskelError };
+pub use i18n::{ Language, MsgKey };
+pub use skeleton::{ generate, plan_relocate, plan_upgrade, ProjectSpec, Report, Skeleton, UpgradeOptions };
@@ -0,0 +1,79 @@
+// 内置许可证表（SPDX 标识 → 菜单名称与正文）
+pub struct License {
+    pub spdx: &'static str,
+    pub label: &'static str,
+    pub text: &'static str,
+    // 正文开头需要填写的版权行；GPL 系列与 Apache 的占位符只出现在附录的使用说明中，保持原样
+    pub copyright: Option<&'static str>,
+}
+
+// 版权行中的占位符：[year] 与 [fullname]
+const COPYRIGHT: &str = "Copyright (c) [year] [fullname]";
+
+pub const LICENSES: &[License] = &[
+    License { spdx: "MIT", label: "MIT", text: include_str!("licenses/mit"), copyright: Some(COPYRIGHT) },
+    License { spdx: "Apache-2.0", label: "Apache-2.0", text: include_str!("licenses/apache-2.0"), copyright: None },
+    License { spdx: "AGPL-3.0", label: "GNU AGPLv3", text: include_str!("licenses/agpl-3.0"), copyright: None },
+    License { spdx: "GPL-3.0", label: "GNU GPLv3", text: include_str!("licenses/gpl-3.0"), copyright: None },
+    License { spdx: "LGPL-3.0", label: "GNU LGPLv3", text: include_str!("licenses/lgpl-3.0"), copyright: None },
+    License {
+        spdx: "MPL-2.0",
+        label: "Mozilla Public License 2.0",
+        text: include_str!("licenses/mpl-2.0"),
+        copyright: None,
+    },
+    License {
+        spdx: "BSL-1.0",
+        label: "Boost Software License 1.0",
+        text: include_str!("licenses/bsl-1.0"),
+        copyright: None,
+    },
+    License { spdx: "Unlicense", label: "Unlicense", text: include_str!("licenses/unlicense"), copyright: None },
+    // 菜单名称按语言显示（MsgKey::Proprietary）
+    License {
+        spdx: "Proprietary",
+        label: "Proprietary",
+        text: "Copyright (c) [year] [fullname]\n\nAll rights reserved.\n",
+        copyright: Some(COPYRIGHT),
+    },
+];
+
+impl License {
+    // 在版权行中填入年份与版权持有人（只替换这一行，附录中的示例保持原样）
+    pub fn render(&self, year: i32, holder: &str) -> String {
+        match self.copyright {
+            Some(line) => {
+                let filled = line.replace("[year]", &year.to_string()).replace("[fullname]", holder);
+                self.text.replacen(line, &filled, 1)
+            }
+            None => self.text.to_string(),
+        }
+    }
+}
+
+// 按 SPDX 标识查找（大小写不敏感，忽略 -only / -or-later 后缀）
+pub fn find(id: &str) -> Option<&'static License> {
+    let id = id.trim();
+    let id = id
+        .strip_suffix("-only")
+        .or_else(|| id.strip_suffix("-or-later"))
+        .unwrap_or(id);
+    LICENSES.iter().find(|l| l.spdx.eq_ignore_ascii_case(id))
+}
+
+// `--license` 的取值校验："none" 表示不生成 LICENSE
+pub fn parse_arg(value: &str) -> Result<String, String> {
+    if value.eq_ignore_ascii_case("none") {
+        return Ok("none".to_string());
+    }
+    match find(value) {
+        Some(license) => Ok(license.spdx.to_string()),
+        None => {
+            let known = LICENSES.iter()
+                .map(|l| l.spdx)
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(format!("unknown license '{}' (expected one of: none, {})", value, known))
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{ Deserialize, Serialize };
+
+use crate::envfiles::EnvFormat;
+use crate::error::{ Result, YuuskelError };
+use crate::generate::PathStyle;
+use crate::helpers::Helper;
+use crate::stack::Stack;
+use crate::template::{ EnvVar, Template };
+use crate::{ Language, MsgKey };
+
+pub const FILE_NAME: &str = "yuuskel.toml";
+
+const HEADER: &str =
+    "# Generated by yuuskel — do not edit manually unless you know what you're doing\n\n";
+
+// yuuskel.toml 中记录的初始化答案（未启用的项写为 false）
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Metadata {
+    pub version: String,
+    // 生成文档所用的语言代码
+    #[serde(default = "default_language")]
+    pub language: String,
+    #[serde(default = "default_template")]
+    pub template: String,
+    // git+<URL> 模板固定的提交
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_commit: Option<String>,
+    #[serde(with = "false_or_string", default)]
+    pub prefix: Option<String>,
+    #[serde(default)]
+    pub env_paths: PathStyle,
+    // .env 之外额外生成的环境文件
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env_formats: Vec<EnvFormat>,
+    // 技术栈预设
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack: Option<Stack>,
+    // 生成的路径辅助模块
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub helpers: Vec<Helper>,
+    // 每个目录中放置 .gitkeep
+    #[serde(default)]
+    pub gitkeep: bool,
+    // 有说明的目录中写入 README.md
+    #[serde(default)]
+    pub dir_readmes: bool,
+    #[serde(default)]
+    pub git_initialized: bool,
+    #[serde(with = "false_or_string", default)]
+    pub license: Option<String>,
+    #[serde(with = "false_or_string", default)]
+    pub holder: Option<String>,
+    #[serde(default)]
+    pub dirs: Vec<String>,
+    // .env 中受管理的变量（不含前缀）及其路径，供 check 与 relocate 使用而不必重新加载模板；旧版本的记录中没有
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env: Vec<EnvVar>,
+    // 模板自定义问题的答案
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub vars: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MetadataFile {
+    yuuskel: Metadata,
+}
+
+fn default_language() -> String {
+    "en".to_string()
+}
+
+fn default_template() -> String {
+    "default".to_string()
+}
+
+pub fn current_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+// 目录中没有 yuuskel.toml
+pub fn not_found(lang: Language, dir: &Path) -> YuuskelError {
+    let path = dir.join(FILE_NAME);
+    YuuskelError::Validation(lang.msg(MsgKey::NotYuuskelProject).replace("{}", &path.display().to_string()))
+}
+
+impl Metadata {
+    // 读取项目根目录下的 yuuskel.toml（不存在时返回 None）
+    pub fn load(dir: &Path) -> Result<Option<Metadata>> {
+        let path = dir.join(FILE_NAME);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path).map_err(|e| YuuskelError::fs(&path, e))?;
+        let file: MetadataFile = toml::from_str(&content).map_err(|e| YuuskelError::config(&path, e))?;
+        Ok(Some(file.yuuskel))
+    }
+
+    // 受管理的变量；旧版本的记录中没有时从（固定在记录的提交的）模板中取得
+    pub fn env_vars(&self) -> Result<Vec<EnvVar>> {
+        if !self.env.is_empty() {
+            return Ok(self.env.clone());
+        }
+        Ok(Template::load_pinned(&self.template, self.template_commit.as_deref())?.env)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let body = toml::to_string(&(MetadataFile { yuuskel: self.clone() })).unwrap_or_default();
+        format!("{}{}", HEADER, body)
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        let path = dir.join(FILE_NAME);
+        fs::write(&path, self.to_toml()).map_err(|e| YuuskelError::fs(&path, e))
+    }
+}
+
+// Option<String> ⇄ "value" / false
+mod false_or_string {
+    use serde::de::Error;
+    use serde::{ Deserialize, Deserializer, Serializer };
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value {
+        Bool(bool),
+        Str(String),
+    }
+
+    pub fn serialize<S: Serializer>(value: &Option<String>, s: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(v) => s.serialize_str(v),
+            None => s.serialize_bool(false),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<String>, D::Error> {
+        match Value::deserialize(d)? {
+            Value::Str(v) => Ok(Some(v)),
+            Value::Bool(false) => Ok(None),
+            Value::Bool(true) => Err(D::Error::custom("expected a string or false")),
+        }
+    }
+}
@@ -0,0 +1,283 @@
+// 先规划、后执行：所有改动先收集为 Plan，dry-run 只打印，正常模式再逐条执行
+use std::fs;
+use std::path::{ Path, PathBuf };
+use std::process::Command;
+use std::sync::Arc;
+use std::sync::atomic::{ AtomicBool, Ordering };
+
+use crate::error::{ Result, YuuskelError };
+use crate::metadata::{ self, Metadata };
+
+pub struct Plan {
+    pub root: PathBuf,
+    pub actions: Vec<Action>,
+}
+
+pub enum Action {
+    // 项目根目录不存在时创建
+    CreateRoot,
+    CreateDir(String),
+    Write(FileWrite),
+    // 重写 .env，附带 managed 变量的差异
+    UpdateEnv {
+        file: FileWrite,
+        changes: Vec<EnvChange>,
+    },
+    // 文件已存在，保持不变
+    Skip(String),
+    WriteMetadata {
+        metadata: Box<Metadata>,
+        existed: bool,
+    },
+    GitInit,
+    GitAdd,
+    GitCommit,
+}
+
+pub struct FileWrite {
+    // 相对项目根目录的路径
+    pub path: String,
+    pub content: String,
+    pub existed: bool,
+}
+
+// managed 变量的变化（old/new 为解析后的值）
+#[derive(Clone)]
+pub struct EnvChange {
+    pub key: String,
+    pub old: Option<String>,
+    pub new: String,
+}
+
+// 单个动作的执行结果（只有 git 步骤允许失败而不中断）
+pub enum Outcome {
+    Done,
+    Failed(YuuskelError),
+    // 前置的 git 步骤失败，跳过
+    Skipped,
+}
+
+pub const COMMIT_MESSAGE: &str = "chore: initialize project with yuuskel";
+
+// 执行失败或被中断时已撤销的改动，以及撤销失败的项
+pub struct Rollback {
+    pub error: YuuskelError,
+    pub steps: Vec<RollbackStep>,
+}
+
+pub enum RollbackStep {
+    // 本次新建的文件或目录已删除
+    Removed(String),
+    // 被覆盖的文件已恢复原内容
+    Restored(String),
+    Failed(String, YuuskelError),
+}
+
+// 已执行改动的撤销方式（按执行顺序记录，回滚时倒序执行）
+enum Undo {
+    RemoveDir(PathBuf),
+    RemoveFile(PathBuf),
+    Restore(PathBuf, Vec<u8>),
+    // git init 新建的 .git
+    RemoveTree(PathBuf),
+}
+
+// 中断标记：由调用方创建并交给 Ctrl-C 处理器等，执行计划时传入 Plan::apply；克隆的副本共享同一状态
+#[derive(Clone, Default)]
+pub struct Interrupt {
+    requested: Arc<AtomicBool>,
+    applying: Arc<AtomicBool>,
+}
+
+impl Interrupt {
+    pub fn new() -> Self {
+        Interrupt::default()
+    }
+
+    // 请求中断：正在执行计划时返回 true（当前动作完成后回滚），否则返回 false
+    pub fn request(&self) -> bool {
+        self.requested.store(true, Ordering::SeqCst);
+        self.applying.load(Ordering::SeqCst)
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+
+    fn check(&self) -> Result<()> {
+        if self.is_requested() { Err(YuuskelError::Interrupted) } else { Ok(()) }
+    }
+}
+
+impl Plan {
+    pub fn new(root: PathBuf) -> Self {
+        Plan { root, actions: Vec::new() }
+    }
+
+    pub fn push(&mut self, action: Action) {
+        self.actions.push(action);
+    }
+
+    // 是否有实际改动（跳过项不算）
+    pub fn has_changes(&self) -> bool {
+        self.actions.iter().any(|action| {
+            match action {
+                Action::Skip(_) => false,
+                Action::UpdateEnv { file, changes } => !file.existed || !changes.is_empty(),
+                _ => true,
+            }
+        })
+    }
+
+    // 依次执行；每完成一个动作回调一次，便于调用方输出进度。
+    // 出错或 interrupt 被请求时撤销已执行的改动：删除新建的文件与目录，恢复被覆盖的文件
+    pub fn apply(
+        &self,
+        interrupt: &Interrupt,
+        report: impl FnMut(&Action, &Outcome)
+    ) -> std::result::Result<(), Rollback> {
+        interrupt.applying.store(true, Ordering::SeqCst);
+        let mut journal = Vec::new();
+        let result = self.run(&mut journal, interrupt, report);
+        interrupt.applying.store(false, Ordering::SeqCst);
+        result.map_err(|error| Rollback { error, steps: self.rollback(journal) })
+    }
+
+    fn run(
+        &self,
+        journal: &mut Vec<Undo>,
+        interrupt: &Interrupt,
+        mut report: impl FnMut(&Action, &Outcome)
+    ) -> Result<()> {
+        let mut git_ok = true;
+        for action in &self.actions {
+            interrupt.check()?;
+            let outcome = match action {
+                Action::CreateRoot => {
+                    create_dir(journal, &self.root)?;
+                    Outcome::Done
+                }
+                Action::CreateDir(dir) => {
+                    create_dir(journal, &self.root.join(dir))?;
+                    Outcome::Done
+                }
+                Action::Write(file) | Action::UpdateEnv { file, .. } => {
+                    let path = self.root.join(&file.path);
+                    if let Some(parent) = path.parent() {
+                        create_dir(journal, parent)?;
+                    }
+                    backup(journal, &path)?;
+                    fs::write(&path, &file.content).map_err(|e| YuuskelError::fs(&path, e))?;
+                    Outcome::Done
+                }
+                Action::Skip(_) => Outcome::Done,
+                Action::WriteMetadata { metadata, .. } => {
+                    let mut metadata = metadata.clone();
+                    metadata.git_initialized &= git_ok;
+                    backup(journal, &self.root.join(metadata::FILE_NAME))?;
+                    metadata.save(&self.root)?;
+                    Outcome::Done
+                }
+                Action::GitInit => {
+                    let git_dir = self.root.join(".git");
+                    if !git_dir.exists() {
+                        journal.push(Undo::RemoveTree(git_dir));
+                    }
+                    self.git(&mut git_ok, "init", &["init"])
+                }
+                Action::GitAdd => self.git(&mut git_ok, "add .", &["add", "."]),
+                Action::GitCommit => self.git(&mut git_ok, "commit", &["commit", "-m", COMMIT_MESSAGE]),
+            };
+            report(action, &outcome);
+        }
+        interrupt.check()
+    }
+
+    fn rollback(&self, journal: Vec<Undo>) -> Vec<RollbackStep> {
+        let mut steps = Vec::new();
+        for undo in journal.into_iter().rev() {
+            let (path, result, restored) = match undo {
+                // 未能创建，或目录中还有其他内容（不是本次写入的）时保留
+                Undo::RemoveDir(path) => {
+                    if !path.is_dir() || fs::read_dir(&path).is_ok_and(|mut e| e.next().is_some()) {
+                        continue;
+                    }
+                    let result = fs::remove_dir(&path);
+                    (path, result, false)
+                }
+                Undo::RemoveFile(path) => {
+                    if !path.exists() {
+                        continue;
+                    }
+                    let result = fs::remove_file(&path);
+                    (path, result, false)
+                }
+                Undo::Restore(path, content) => {
+                    let result = fs::write(&path, content);
+                    (path, result, true)
+                }
+                Undo::RemoveTree(path) => {
+                    if !path.exists() {
+                        continue;
+                    }
+                    let result = fs::remove_dir_all(&path);
+                    (path, result, false)
+                }
+            };
+            // 项目内的路径显示为相对路径
+            let shown = match path.strip_prefix(&self.root) {
+                Ok(rel) if !rel.as_os_str().is_empty() => rel.to_string_lossy().replace('\\', "/"),
+                _ => path.display().to_string(),
+            };
+            steps.push(match result {
+                Ok(()) if restored => RollbackStep::Restored(shown),
+                Ok(()) => RollbackStep::Removed(shown),
+                Err(e) => RollbackStep::Failed(shown, YuuskelError::fs(&path, e)),
+            });
+        }
+        steps
+    }
+
+    // command 为错误消息中显示的子命令
+    fn git(&self, git_ok: &mut bool, command: &str, args: &[&str]) -> Outcome {
+        if !*git_ok {
+            return Outcome::Skipped;
+        }
+        let outcome = match Command::new("git").args(args).current_dir(&self.root).output() {
+            Ok(output) if output.status.success() => Outcome::Done,
+            Ok(output) =>
+                Outcome::Failed(YuuskelError::Git {
+                    command: command.to_string(),
+                    path: self.root.clone(),
+                    detail: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+                }),
+            Err(e) => Outcome::Failed(YuuskelError::GitUnavailable(e)),
+        };
+        *git_ok = matches!(outcome, Outcome::Done);
+        outcome
+    }
+}
+
+// 创建目录，并记录本次新建的每一级目录（先记录，创建到一半失败时也能回滚）
+fn create_dir(journal: &mut Vec<Undo>, path: &Path) -> Result<()> {
+    let missing: Vec<&Path> = path
+        .ancestors()
+        .take_while(|p| !p.as_os_str().is_empty() && !p.exists())
+        .collect();
+    journal.extend(missing.into_iter().rev().map(|p| Undo::RemoveDir(p.to_path_buf())));
+    fs::create_dir_all(path).map_err(|e| YuuskelError::fs(path, e))
+}
+
+// 写入前备份已有文件；文件不存在时记录为新建
+fn backup(journal: &mut Vec<Undo>, path: &Path) -> Result<()> {
+    match fs::read(path) {
+        Ok(content) => journal.push(Undo::Restore(path.to_path_buf(), content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            journal.push(Undo::RemoveFile(path.to_path_buf()));
+        }
+        Err(e) => {
+            return Err(YuuskelError::fs(path, e));
+        }
+    }
+    Ok(())
+}
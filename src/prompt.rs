@@ -0,0 +1,374 @@
+// 所有交互问题的统一入口：终端交互、预先给出的答案（参数）与 --yes 的默认答案都实现 Prompter
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+
+use dialoguer::{ theme::ColorfulTheme, Confirm, Input, Select };
+
+use yuuskel::{ Result, YuuskelError };
+
+// 一个问题：key 用于查找预先给出的答案，flag 为无法交互时提示的参数
+pub struct Question<'a> {
+    pub key: &'a str,
+    pub flag: &'a str,
+    pub message: &'a str,
+}
+
+// 输入校验：返回的错误信息在交互时显示并要求重新输入
+pub type Validate<'a> = &'a dyn Fn(&str) -> std::result::Result<(), String>;
+
+pub trait Prompter {
+    // choices 为 (答案, 显示文本)，返回所选项的序号
+    fn select(&mut self, question: &Question, choices: &[(&str, &str)], default: usize) -> Result<usize>;
+
+    fn confirm(&mut self, question: &Question, default: bool) -> Result<bool>;
+
+    fn input(&mut self, question: &Question, default: Option<&str>, validate: Validate) -> Result<String>;
+
+    // 是否预先给出了该问题的答案（用于“给出时才生效”的问题，如已有项目中的 --license）
+    fn has_answer(&self, _key: &str) -> bool {
+        false
+    }
+
+    // 不经过提问确定的答案（如自动检测的语言、取自 git 的版权持有人），供 Recorder 一并保存
+    fn record(&mut self, _key: &str, _answer: &str) {}
+}
+
+// 终端交互（统一使用 ColorfulTheme）；标准输入不是终端时报告缺少哪个参数
+pub struct Terminal {
+    theme: ColorfulTheme,
+}
+
+impl Terminal {
+    pub fn new() -> Self {
+        Terminal { theme: ColorfulTheme::default() }
+    }
+}
+
+fn ensure_interactive(question: &Question) -> Result<()> {
+    if std::io::stdin().is_terminal() {
+        Ok(())
+    } else {
+        Err(YuuskelError::MissingAnswer(question.flag.to_string()))
+    }
+}
+
+impl Prompter for Terminal {
+    fn select(&mut self, question: &Question, choices: &[(&str, &str)], default: usize) -> Result<usize> {
+        ensure_interactive(question)?;
+        Ok(
+            Select::with_theme(&self.theme)
+                .with_prompt(question.message)
+                .items(choices.iter().map(|(_, label)| *label))
+                .default(default)
+                .interact()?
+        )
+    }
+
+    fn confirm(&mut self, question: &Question, default: bool) -> Result<bool> {
+        ensure_interactive(question)?;
+        Ok(Confirm::with_theme(&self.theme).with_prompt(question.message).default(default).interact()?)
+    }
+
+    fn input(&mut self, question: &Question, default: Option<&str>, validate: Validate) -> Result<String> {
+        ensure_interactive(question)?;
+        let mut input = Input::<String>
+            ::with_theme(&self.theme)
+            .with_prompt(question.message)
+            .allow_empty(default.is_some())
+            .validate_with(|value: &String| validate(value));
+        if let Some(default) = default {
+            input = input.default(default.to_string());
+        }
+        Ok(input.interact_text()?)
+    }
+}
+
+// --yes：所有问题使用默认答案，没有默认值的输入视为缺少答案
+pub struct Defaults;
+
+impl Prompter for Defaults {
+    fn select(&mut self, _: &Question, _: &[(&str, &str)], default: usize) -> Result<usize> {
+        Ok(default)
+    }
+
+    fn confirm(&mut self, _: &Question, default: bool) -> Result<bool> {
+        Ok(default)
+    }
+
+    fn input(&mut self, question: &Question, default: Option<&str>, _: Validate) -> Result<String> {
+        default.map(str::to_string).ok_or_else(|| YuuskelError::MissingAnswer(question.flag.to_string()))
+    }
+}
+
+// 预先给出的答案（按问题的 key），其余问题交给 fallback
+pub struct Preanswered<P> {
+    answers: BTreeMap<String, String>,
+    fallback: P,
+}
+
+impl<P: Prompter> Preanswered<P> {
+    pub fn new(answers: BTreeMap<String, String>, fallback: P) -> Self {
+        Preanswered { answers, fallback }
+    }
+}
+
+impl<P: Prompter> Prompter for Preanswered<P> {
+    fn select(&mut self, question: &Question, choices: &[(&str, &str)], default: usize) -> Result<usize> {
+        match self.answers.get(question.key) {
+            Some(answer) => choose(question, choices, answer),
+            None => self.fallback.select(question, choices, default),
+        }
+    }
+
+    fn confirm(&mut self, question: &Question, default: bool) -> Result<bool> {
+        match self.answers.get(question.key) {
+            Some(answer) => parse_bool(question, answer),
+            None => self.fallback.confirm(question, default),
+        }
+    }
+
+    fn input(&mut self, question: &Question, default: Option<&str>, validate: Validate) -> Result<String> {
+        match self.answers.get(question.key) {
+            Some(answer) => accept(answer, validate),
+            None => self.fallback.input(question, default, validate),
+        }
+    }
+
+    fn has_answer(&self, key: &str) -> bool {
+        self.answers.contains_key(key) || self.fallback.has_answer(key)
+    }
+}
+
+impl<P: Prompter + ?Sized> Prompter for Box<P> {
+    fn select(&mut self, question: &Question, choices: &[(&str, &str)], default: usize) -> Result<usize> {
+        (**self).select(question, choices, default)
+    }
+
+    fn confirm(&mut self, question: &Question, default: bool) -> Result<bool> {
+        (**self).confirm(question, default)
+    }
+
+    fn input(&mut self, question: &Question, default: Option<&str>, validate: Validate) -> Result<String> {
+        (**self).input(question, default, validate)
+    }
+
+    fn has_answer(&self, key: &str) -> bool {
+        (**self).has_answer(key)
+    }
+
+    fn record(&mut self, key: &str, answer: &str) {
+        (**self).record(key, answer)
+    }
+}
+
+// 记录每个问题最终的答案（--save-answers），答案的写法与 Preanswered 接受的一致
+pub struct Recorder<P> {
+    inner: P,
+    answers: BTreeMap<String, String>,
+}
+
+impl<P: Prompter> Recorder<P> {
+    pub fn new(inner: P) -> Self {
+        Recorder { inner, answers: BTreeMap::new() }
+    }
+
+    pub fn answers(&self) -> &BTreeMap<String, String> {
+        &self.answers
+    }
+}
+
+impl<P: Prompter> Prompter for Recorder<P> {
+    fn select(&mut self, question: &Question, choices: &[(&str, &str)], default: usize) -> Result<usize> {
+        let index = self.inner.select(question, choices, default)?;
+        self.record(question.key, choices[index].0);
+        Ok(index)
+    }
+
+    fn confirm(&mut self, question: &Question, default: bool) -> Result<bool> {
+        let answer = self.inner.confirm(question, default)?;
+        self.record(question.key, if answer { "yes" } else { "no" });
+        Ok(answer)
+    }
+
+    fn input(&mut self, question: &Question, default: Option<&str>, validate: Validate) -> Result<String> {
+        let answer = self.inner.input(question, default, validate)?;
+        self.record(question.key, &answer);
+        Ok(answer)
+    }
+
+    fn has_answer(&self, key: &str) -> bool {
+        self.inner.has_answer(key)
+    }
+
+    fn record(&mut self, key: &str, answer: &str) {
+        self.answers.insert(key.to_string(), answer.to_string());
+    }
+}
+
+// 按答案（不区分大小写）找到对应的选项
+fn choose(question: &Question, choices: &[(&str, &str)], answer: &str) -> Result<usize> {
+    let answer = answer.trim();
+    choices
+        .iter()
+        .position(|(key, _)| key.eq_ignore_ascii_case(answer))
+        .ok_or_else(|| YuuskelError::InvalidAnswer {
+            question: question.key.to_string(),
+            answer: answer.to_string(),
+            expected: choices
+                .iter()
+                .map(|(key, _)| *key)
+                .collect::<Vec<_>>()
+                .join(", "),
+        })
+}
+
+fn parse_bool(question: &Question, answer: &str) -> Result<bool> {
+    match answer.trim().to_ascii_lowercase().as_str() {
+        "yes" | "y" | "true" => Ok(true),
+        "no" | "n" | "false" => Ok(false),
+        _ =>
+            Err(YuuskelError::InvalidAnswer {
+                question: question.key.to_string(),
+                answer: answer.to_string(),
+                expected: "yes, no".to_string(),
+            }),
+    }
+}
+
+fn accept(answer: &str, validate: Validate) -> Result<String> {
+    validate(answer).map_err(YuuskelError::Validation)?;
+    Ok(answer.to_string())
+}
+
+// 测试用：按顺序给出答案，并检查问题的顺序与脚本一致
+#[cfg(test)]
+pub struct Scripted {
+    script: std::collections::VecDeque<(&'static str, String)>,
+}
+
+#[cfg(test)]
+impl Scripted {
+    pub fn new(script: &[(&'static str, &str)]) -> Self {
+        Scripted { script: script.iter().map(|(key, answer)| (*key, answer.to_string())).collect() }
+    }
+
+    fn next(&mut self, question: &Question) -> Result<String> {
+        match self.script.pop_front() {
+            Some((key, answer)) => {
+                assert_eq!(key, question.key, "unexpected question");
+                Ok(answer)
+            }
+            None => Err(YuuskelError::MissingAnswer(question.flag.to_string())),
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.script.is_empty()
+    }
+}
+
+#[cfg(test)]
+impl Prompter for Scripted {
+    fn select(&mut self, question: &Question, choices: &[(&str, &str)], _: usize) -> Result<usize> {
+        let answer = self.next(question)?;
+        choose(question, choices, &answer)
+    }
+
+    fn confirm(&mut self, question: &Question, _: bool) -> Result<bool> {
+        let answer = self.next(question)?;
+        parse_bool(question, &answer)
+    }
+
+    fn input(&mut self, question: &Question, _: Option<&str>, validate: Validate) -> Result<String> {
+        let answer = self.next(question)?;
+        accept(&answer, validate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn question(key: &'static str) -> Question<'static> {
+        Question { key, flag: "--flag", message: key }
+    }
+
+    const STACKS: [(&str, &str); 3] = [("none", "None"), ("python", "Python"), ("rust", "Rust")];
+
+    #[test]
+    fn preanswered_takes_precedence() {
+        let answers = BTreeMap::from([
+            ("stack".to_string(), "Rust".to_string()),
+            ("git".to_string(), "no".to_string()),
+            ("name".to_string(), "demo".to_string()),
+        ]);
+        let mut prompter = Preanswered::new(answers, Defaults);
+
+        assert_eq!(prompter.select(&question("stack"), &STACKS, 1).unwrap(), 2);
+        assert!(!prompter.confirm(&question("git"), true).unwrap());
+        assert_eq!(prompter.input(&question("name"), Some("x"), &|_| Ok(())).unwrap(), "demo");
+        assert!(prompter.has_answer("stack"));
+        assert!(!prompter.has_answer("license"));
+
+        // 未给出的问题交给 fallback（这里是默认答案）
+        assert_eq!(prompter.select(&question("license"), &STACKS, 1).unwrap(), 1);
+        assert!(prompter.confirm(&question("commit"), true).unwrap());
+    }
+
+    #[test]
+    fn invalid_answers() {
+        let answers = BTreeMap::from([
+            ("stack".to_string(), "cobol".to_string()),
+            ("git".to_string(), "maybe".to_string()),
+            ("name".to_string(), "CON".to_string()),
+        ]);
+        let mut prompter = Preanswered::new(answers, Defaults);
+
+        let e = prompter.select(&question("stack"), &STACKS, 0).unwrap_err();
+        assert_eq!(e.to_string(), "invalid answer for stack: 'cobol' (expected one of: none, python, rust)");
+        assert_eq!(e.exit_code(), 2);
+        assert!(matches!(prompter.confirm(&question("git"), false), Err(YuuskelError::InvalidAnswer { .. })));
+        let validate = |name: &str| if name == "CON" { Err("reserved".to_string()) } else { Ok(()) };
+        assert!(
+            matches!(prompter.input(&question("name"), None, &validate), Err(YuuskelError::Validation(m)) if m == "reserved")
+        );
+    }
+
+    #[test]
+    fn defaults_without_default_is_missing() {
+        let e = Defaults.input(&question("holder"), None, &|_| Ok(())).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "missing answer for --flag (not running in a terminal; pass it as a flag or use --yes)"
+        );
+    }
+
+    #[test]
+    fn recorder_keeps_final_answers() {
+        let answers = BTreeMap::from([("stack".to_string(), "RUST".to_string())]);
+        let mut prompter = Recorder::new(Preanswered::new(answers, Defaults));
+
+        prompter.select(&question("stack"), &STACKS, 0).unwrap();
+        prompter.confirm(&question("git"), true).unwrap();
+        prompter.input(&question("name"), Some("demo"), &|_| Ok(())).unwrap();
+        prompter.record("language", "ja");
+
+        let recorded: Vec<_> = prompter
+            .answers()
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        assert_eq!(recorded, [("git", "yes"), ("language", "ja"), ("name", "demo"), ("stack", "rust")]);
+    }
+
+    #[test]
+    fn scripted_answers_in_order() {
+        let mut prompter = Scripted::new(&[("stack", "python"), ("commit", "yes"), ("prefix", "LAB")]);
+
+        assert_eq!(prompter.select(&question("stack"), &STACKS, 0).unwrap(), 1);
+        assert!(prompter.confirm(&question("commit"), false).unwrap());
+        assert_eq!(prompter.input(&question("prefix"), None, &|_| Ok(())).unwrap(), "LAB");
+        assert!(prompter.is_done());
+        assert!(matches!(prompter.confirm(&question("git"), false), Err(YuuskelError::MissingAnswer(_))));
+    }
+}
@@ -0,0 +1,62 @@
+// `yuuskel relocate`（别名 sync-env）：项目移动后，按新的根目录重写 .env（及其他环境文件）中的 managed 路径
+use colored::*;
+
+use crate::cli::RelocateArgs;
+use yuuskel::error::Result;
+use yuuskel::generate;
+use yuuskel::metadata::{ self, Metadata };
+use yuuskel::plan::{ Action, Interrupt };
+use crate::report;
+use yuuskel::skeleton;
+use yuuskel::{ Language, MsgKey };
+
+pub fn run(lang: Language, args: &RelocateArgs, interrupt: &Interrupt) -> Result<()> {
+    let target_dir = &args.dir;
+    let metadata = Metadata::load(target_dir)?.ok_or_else(|| {
+        metadata::not_found(lang, target_dir)
+    })?;
+    let abs_str = generate::project_root(target_dir);
+    let plan = skeleton::plan_relocate(target_dir, metadata, args.env_paths)?;
+
+    let Some(Action::UpdateEnv { changes, .. }) = plan.actions.first() else {
+        unreachable!("plan_env always plans the .env update");
+    };
+    if !plan.has_changes() {
+        println!("{}", lang.msg(MsgKey::RelocateInSync).green().bold());
+        return Ok(());
+    }
+
+    // 旧的 PROJECT_ROOT 与当前位置不同 → 项目被移动过
+    let old_root = changes
+        .iter()
+        .find(|c| c.key == "PROJECT_ROOT")
+        .and_then(|c| c.old.clone());
+    if let Some(old_root) = old_root.filter(|r| r != "." && *r != abs_str) {
+        println!(
+            "{}",
+            lang
+                .msg(MsgKey::RelocateMoved)
+                .replacen("{}", &old_root, 1)
+                .replacen("{}", &abs_str, 1)
+                .yellow()
+        );
+    }
+
+    let count = changes.len();
+    if args.dry_run {
+        report::print_plan(lang, &plan);
+        return Ok(());
+    }
+
+    // 其他环境文件逐个提示
+    plan.apply(interrupt, |action, outcome| {
+        if let Action::Write(_) = action {
+            report::print_step(lang, true, action, outcome);
+        }
+    }).map_err(|rollback| {
+        report::print_rollback(lang, &rollback.steps);
+        rollback.error
+    })?;
+    println!("{}", lang.msg(MsgKey::RelocateDone).replace("{}", &count.to_string()).green().bold());
+    Ok(())
+}
@@ -0,0 +1,205 @@
+// 远程模板：git+<URL>[#<ref>] 形式的来源用系统的 git 克隆到用户缓存目录，按提交固定
+use std::env;
+use std::fs;
+use std::path::{ Path, PathBuf };
+use std::process::Command;
+
+use crate::error::{ Result, YuuskelError };
+
+const SCHEME: &str = "git+";
+
+// 解析后的来源，如 git+https://github.com/lab/templates.git#v1
+pub struct Remote {
+    pub url: String,
+    // 分支、标签或提交；未指定时使用远程的默认分支
+    pub reference: Option<String>,
+}
+
+// 缓存中的一个模板仓库
+pub struct Cached {
+    pub dir: PathBuf,
+    pub url: String,
+    // 当前检出的提交
+    pub commit: String,
+}
+
+pub fn is_remote(source: &str) -> bool {
+    source.starts_with(SCHEME)
+}
+
+// 提交的短写（最多 7 位）
+pub fn short(commit: &str) -> &str {
+    &commit[..commit.len().min(7)]
+}
+
+impl Remote {
+    pub fn parse(source: &str) -> Option<Remote> {
+        let rest = source.strip_prefix(SCHEME)?;
+        let (url, reference) = match rest.split_once('#') {
+            Some((url, reference)) => (url, Some(reference.to_string()).filter(|r| !r.is_empty())),
+            None => (rest, None),
+        };
+        if url.is_empty() {
+            return None;
+        }
+        Some(Remote { url: url.to_string(), reference })
+    }
+
+    // 缓存中的目录名：去掉协议部分，其余非字母数字的字符替换为 _（同一仓库的不同 ref 共用一份克隆）
+    fn key(&self) -> String {
+        let path = self.url.split_once("://").map_or(self.url.as_str(), |(_, rest)| rest);
+        path.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+            .collect::<String>()
+            .trim_matches('_')
+            .to_string()
+    }
+}
+
+// 缓存目录：$XDG_CACHE_HOME/yuuskel/templates，其次 %LOCALAPPDATA%\yuuskel\templates（Windows）或 ~/.cache/yuuskel/templates
+pub fn cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_CACHE_HOME").filter(|v| !v.is_empty()) {
+        return Some(PathBuf::from(dir).join("yuuskel").join("templates"));
+    }
+    #[cfg(windows)]
+    if let Some(dir) = env::var_os("LOCALAPPDATA").filter(|v| !v.is_empty()) {
+        return Some(PathBuf::from(dir).join("yuuskel").join("templates"));
+    }
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache").join("yuuskel").join("templates"))
+}
+
+// 取得模板仓库：未缓存时克隆；检出给出的 commit 或 ref（缓存中没有则先拉取），
+// 都未给出时检出远程的默认分支（不访问网络，更新见 update）
+pub fn fetch(source: &str, commit: Option<&str>) -> Result<Cached> {
+    let remote = Remote::parse(source).ok_or_else(|| YuuskelError::template(source, "expected git+<URL>"))?;
+    let root = cache_dir().ok_or_else(|| {
+        YuuskelError::template(source, "cannot determine the user cache directory (set XDG_CACHE_HOME)")
+    })?;
+    let dir = root.join(remote.key());
+    if !dir.join(".git").is_dir() {
+        fs::create_dir_all(&root).map_err(|e| YuuskelError::fs(&root, e))?;
+        git(&root, "clone", &["clone", "--quiet", "--", &remote.url, &remote.key()])?;
+    }
+
+    let target = match (commit, &remote.reference) {
+        (Some(commit), _) => {
+            if resolve(&dir, commit).is_none() {
+                git(&dir, "fetch", &["fetch", "--quiet", "--tags", "origin"])?;
+            }
+            resolve(&dir, commit).ok_or_else(|| {
+                YuuskelError::template(source, format!("commit {} not found in {}", commit, remote.url))
+            })?
+        }
+        (None, Some(reference)) => {
+            let find = || resolve(&dir, &format!("origin/{}", reference)).or_else(|| resolve(&dir, reference));
+            if find().is_none() {
+                git(&dir, "fetch", &["fetch", "--quiet", "--tags", "origin"])?;
+            }
+            find().ok_or_else(|| {
+                YuuskelError::template(source, format!("no branch, tag or commit '{}' in {}", reference, remote.url))
+            })?
+        }
+        (None, None) =>
+            resolve(&dir, "origin/HEAD").ok_or_else(|| {
+                YuuskelError::template(source, format!("{} has no default branch", remote.url))
+            })?,
+    };
+    git(&dir, "checkout", &["checkout", "--quiet", "--detach", &target])?;
+    Ok(Cached { url: remote.url, commit: target, dir })
+}
+
+// 从远程拉取，并检出默认分支的最新提交；返回更新后的状态
+pub fn update(cached: &Cached) -> Result<Cached> {
+    git(&cached.dir, "fetch", &["fetch", "--quiet", "--tags", "--prune", "origin"])?;
+    // 远程的默认分支可能已改变
+    git(&cached.dir, "remote set-head", &["remote", "set-head", "origin", "--auto"]).ok();
+    let commit = resolve(&cached.dir, "origin/HEAD").ok_or_else(|| {
+        YuuskelError::template(&cached.url, "the remote has no default branch")
+    })?;
+    git(&cached.dir, "checkout", &["checkout", "--quiet", "--detach", &commit])?;
+    Ok(Cached { dir: cached.dir.clone(), url: cached.url.clone(), commit })
+}
+
+// 缓存中的全部模板仓库（按目录名排序）
+pub fn cached() -> Result<Vec<Cached>> {
+    let Some(root) = cache_dir().filter(|dir| dir.is_dir()) else {
+        return Ok(Vec::new());
+    };
+    let mut dirs: Vec<PathBuf> = fs
+        ::read_dir(&root)
+        .map_err(|e| YuuskelError::fs(&root, e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.join(".git").is_dir())
+        .collect();
+    dirs.sort();
+    dirs.into_iter().map(open).collect()
+}
+
+// 按来源查找缓存：git+<URL>、<URL>（可带 #ref）或缓存目录名
+pub fn find(source: &str) -> Result<Option<Cached>> {
+    let source = source.strip_prefix(SCHEME).unwrap_or(source);
+    let key = Remote::parse(&format!("{}{}", SCHEME, source)).map(|r| r.key());
+    for cached in cached()? {
+        let name = cached.dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+        if Some(&name) == key.as_ref() || name == source {
+            return Ok(Some(cached));
+        }
+    }
+    Ok(None)
+}
+
+pub fn remove(cached: &Cached) -> Result<()> {
+    fs::remove_dir_all(&cached.dir).map_err(|e| YuuskelError::fs(&cached.dir, e))
+}
+
+fn open(dir: PathBuf) -> Result<Cached> {
+    let url = git(&dir, "config", &["config", "--get", "remote.origin.url"])?;
+    let commit = git(&dir, "rev-parse", &["rev-parse", "HEAD"])?;
+    Ok(Cached { dir, url, commit })
+}
+
+// 解析为完整的提交哈希（不存在时返回 None）
+fn resolve(dir: &Path, rev: &str) -> Option<String> {
+    git(dir, "rev-parse", &["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", rev)]).ok()
+}
+
+// 运行 git 并返回标准输出；command 为错误消息中显示的子命令
+fn git(dir: &Path, command: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new("git").args(args).current_dir(dir).output().map_err(YuuskelError::GitUnavailable)?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(YuuskelError::Git {
+            command: command.to_string(),
+            path: dir.to_path_buf(),
+            detail: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sources() {
+        let remote = Remote::parse("git+https://github.com/lab/templates.git#v1").unwrap();
+        assert_eq!(remote.url, "https://github.com/lab/templates.git");
+        assert_eq!(remote.reference.as_deref(), Some("v1"));
+        assert_eq!(remote.key(), "github.com_lab_templates.git");
+
+        let remote = Remote::parse("git+git@github.com:lab/templates.git").unwrap();
+        assert_eq!(remote.reference, None);
+        assert_eq!(remote.key(), "git_github.com_lab_templates.git");
+
+        assert!(Remote::parse("git+").is_none());
+        assert!(Remote::parse("./templates/lab").is_none());
+    }
+
+    #[test]
+    fn shortens_commits() {
+        assert_eq!(short("0123456789abcdef"), "0123456");
+        assert_eq!(short("abc"), "abc");
+        assert_eq!(short(""), "");
+    }
+}
@@ -0,0 +1,131 @@
+// 计划与执行进度的终端输出
+use colored::*;
+
+use yuuskel::metadata::FILE_NAME;
+use yuuskel::plan::{ Action, COMMIT_MESSAGE, Outcome, Plan, RollbackStep };
+use yuuskel::{ Language, MsgKey };
+
+// 执行过程中每完成一个动作输出一行
+pub fn print_step(lang: Language, is_existing: bool, action: &Action, outcome: &Outcome) {
+    match (action, outcome) {
+        (Action::CreateDir(d), Outcome::Done) => {
+            if is_existing {
+                println!("{}{}", lang.msg(MsgKey::AddDir), d.yellow());
+            } else {
+                println!("{}{}", lang.msg(MsgKey::CreateDir), d.green());
+            }
+        }
+        (Action::UpdateEnv { file, .. }, Outcome::Done) => {
+            if file.existed {
+                println!("{}{}", lang.msg(MsgKey::UpdateDotEnv), file.path.blue());
+            } else {
+                println!("➕ {}", file.path.green());
+            }
+        }
+        (Action::Write(file), Outcome::Done) => {
+            if file.existed {
+                println!("{}{}", lang.msg(MsgKey::UpdateDotEnv), file.path.blue());
+            } else {
+                println!("➕ {}", file.path.green());
+            }
+        }
+        (Action::Skip(path), Outcome::Done) if is_existing => {
+            println!("{}", lang.msg(MsgKey::SkipExisting).replace("{}", path).blue());
+        }
+        (Action::GitInit, Outcome::Done) => {
+            println!("{}", lang.msg(MsgKey::GitInitialized).green());
+        }
+        (Action::GitCommit, Outcome::Done) => {
+            println!("{}", lang.msg(MsgKey::CommitSuccess));
+        }
+        // git 步骤失败只警告，不中断
+        (Action::GitAdd, Outcome::Failed(e)) => {
+            eprintln!("⚠️  {}", e.message(lang));
+            eprintln!("{}", lang.msg(MsgKey::GitAddHint));
+        }
+        (_, Outcome::Failed(e)) => {
+            eprintln!("⚠️  {}", e.message(lang));
+        }
+        _ => {}
+    }
+}
+
+// 执行失败后的回滚结果
+pub fn print_rollback(lang: Language, steps: &[RollbackStep]) {
+    if steps.is_empty() {
+        return;
+    }
+    eprintln!("\n{}", lang.msg(MsgKey::RollbackHeader).yellow().bold());
+    for step in steps {
+        match step {
+            RollbackStep::Removed(path) => {
+                eprintln!("{}", lang.msg(MsgKey::RollbackRemoved).replace("{}", path));
+            }
+            RollbackStep::Restored(path) => {
+                eprintln!("{}", lang.msg(MsgKey::RollbackRestored).replace("{}", path));
+            }
+            RollbackStep::Failed(path, e) => {
+                let msg = lang
+                    .msg(MsgKey::RollbackFailed)
+                    .replacen("{}", path, 1)
+                    .replacen("{}", &e.message(lang), 1);
+                eprintln!("{}", msg.red());
+            }
+        }
+    }
+}
+
+// --dry-run：打印完整计划（+ 新建，~ 修改，= 保持不变）
+pub fn print_plan(lang: Language, plan: &Plan) {
+    println!("\n{}", lang.msg(MsgKey::DryRunHeader).cyan().bold());
+    if !plan.has_changes() {
+        println!("{}", lang.msg(MsgKey::DryRunNoChanges).dimmed());
+    }
+    for action in &plan.actions {
+        match action {
+            Action::CreateRoot => {
+                println!("  {} {}/", "+".green(), plan.root.display());
+            }
+            Action::CreateDir(d) => {
+                println!("  {} {}/", "+".green(), d);
+            }
+            Action::Write(file) => {
+                if file.existed {
+                    println!("  {} {}", "~".yellow(), file.path);
+                } else {
+                    println!("  {} {}", "+".green(), file.path);
+                }
+            }
+            Action::UpdateEnv { file, changes } => {
+                if !file.existed {
+                    println!("  {} {}", "+".green(), file.path);
+                } else if changes.is_empty() {
+                    println!("  {} {}", "=".dimmed(), file.path.dimmed());
+                } else {
+                    println!("  {} {}", "~".yellow(), file.path);
+                }
+                for change in changes {
+                    let new = format!("{}={:?}", change.key, change.new);
+                    if let Some(old) = &change.old {
+                        let old = format!("{}={:?}", change.key, old);
+                        println!("      {} {}", "-".red(), old.red());
+                    }
+                    println!("      {} {}", "+".green(), new.green());
+                }
+            }
+            Action::Skip(path) => {
+                println!("  {} {}", "=".dimmed(), path.dimmed());
+            }
+            Action::WriteMetadata { existed, .. } => {
+                if *existed {
+                    println!("  {} {}", "~".yellow(), FILE_NAME);
+                } else {
+                    println!("  {} {}", "+".green(), FILE_NAME);
+                }
+            }
+            Action::GitInit => println!("  {} git init", "+".green()),
+            Action::GitAdd => println!("  {} git add .", "+".green()),
+            Action::GitCommit => println!("  {} git commit -m {:?}", "+".green(), COMMIT_MESSAGE),
+        }
+    }
+}
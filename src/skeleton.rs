@@ -0,0 +1,593 @@
+// 生成项目骨架的库接口：用 Skeleton 描述要生成的内容，得到 ProjectSpec，
+// 再规划并执行（本模块不做任何终端输入输出，进度通过回调交给调用方）
+use std::collections::BTreeMap;
+use std::path::{ Path, PathBuf };
+
+use chrono::Datelike;
+
+use crate::{ Language, MsgKey };
+use crate::envfiles::EnvFormat;
+use crate::error::{ Result, YuuskelError };
+use crate::generate::{ self, PathStyle };
+use crate::helpers::Helper;
+use crate::license::License;
+use crate::metadata::{ self, Metadata };
+use crate::plan::{ Action, EnvChange, FileWrite, Interrupt, Outcome, Plan, Rollback };
+use crate::stack::Stack;
+use crate::template::{ self, Context, Template };
+
+// 完整的生成描述（所有答案都已确定）
+pub struct ProjectSpec {
+    pub target_dir: PathBuf,
+    pub template: Template,
+    pub language: Language,
+    // 不含末尾的 _，如 MYPROJ
+    pub prefix: Option<String>,
+    pub env_paths: PathStyle,
+    pub env_formats: Vec<EnvFormat>,
+    pub helpers: Vec<Helper>,
+    pub stack: Option<Stack>,
+    pub gitkeep: bool,
+    pub dir_readmes: bool,
+    pub license: Option<&'static License>,
+    pub holder: Option<String>,
+    pub author: Option<String>,
+    pub author_email: Option<String>,
+    // 模板问题的答案
+    pub vars: BTreeMap<String, String>,
+    pub git: bool,
+    pub commit: bool,
+}
+
+// 新建项目文件夹的名称是否合法（不含路径分隔符、不是 Windows 保留名等）
+pub fn validate_project_name(name: &str) -> bool {
+    if name.is_empty() || name.len() > 100 {
+        return false;
+    }
+    if name == "." || name == ".." {
+        return false;
+    }
+    // 禁止路径分隔符（跨平台）
+    if name.contains(std::path::MAIN_SEPARATOR) || name.contains('/') || name.contains('\\') {
+        return false;
+    }
+    // 禁止 Windows 保留名（大小写不敏感）
+    let reserved = [
+        "CON",
+        "PRN",
+        "AUX",
+        "NUL",
+        "COM1",
+        "COM2",
+        "COM3",
+        "COM4",
+        "COM5",
+        "COM6",
+        "COM7",
+        "COM8",
+        "COM9",
+        "LPT1",
+        "LPT2",
+        "LPT3",
+        "LPT4",
+        "LPT5",
+        "LPT6",
+        "LPT7",
+        "LPT8",
+        "LPT9",
+    ];
+    if reserved.contains(&name.to_uppercase().as_str()) {
+        return false;
+    }
+    // 可选：禁止首尾空格或点（避免隐藏目录）
+    if
+        name.starts_with(|c: char| c.is_whitespace() || c == '.') ||
+        name.ends_with(|c: char| c.is_whitespace())
+    {
+        return false;
+    }
+    true
+}
+
+// 环境变量前缀是否合法：[A-Za-z_][A-Za-z0-9_]*（shell 变量名的规则）
+pub fn validate_prefix(prefix: &str) -> bool {
+    let mut chars = prefix.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_') &&
+        chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// ProjectSpec 的构建器，未设置的项使用默认值（default 模板、English、不加前缀、不初始化 Git）
+pub struct Skeleton {
+    target_dir: PathBuf,
+    template: Option<Template>,
+    dirs: Vec<String>,
+    env: Vec<(String, String)>,
+    language: Language,
+    prefix: Option<String>,
+    env_paths: PathStyle,
+    env_formats: Vec<EnvFormat>,
+    helpers: Vec<Helper>,
+    stack: Option<Stack>,
+    gitkeep: bool,
+    dir_readmes: bool,
+    license: Option<&'static License>,
+    holder: Option<String>,
+    author: Option<String>,
+    author_email: Option<String>,
+    vars: BTreeMap<String, String>,
+    git: bool,
+    commit: bool,
+}
+
+impl Skeleton {
+    pub fn new(target_dir: impl Into<PathBuf>) -> Self {
+        Skeleton {
+            target_dir: target_dir.into(),
+            template: None,
+            dirs: Vec::new(),
+            env: Vec::new(),
+            language: Language::En,
+            prefix: None,
+            env_paths: PathStyle::default(),
+            env_formats: Vec::new(),
+            helpers: Vec::new(),
+            stack: None,
+            gitkeep: false,
+            dir_readmes: false,
+            license: None,
+            holder: None,
+            author: None,
+            author_email: None,
+            vars: BTreeMap::new(),
+            git: false,
+            commit: false,
+        }
+    }
+
+    pub fn template(mut self, template: Template) -> Self {
+        self.template = Some(template);
+        self
+    }
+
+    // 追加模板之外的目录
+    pub fn dir(mut self, path: impl Into<String>) -> Self {
+        self.dirs.push(path.into());
+        self
+    }
+
+    // 追加 .env 中的路径变量（key 不含前缀）
+    pub fn env_var(mut self, key: impl Into<String>, path: impl Into<String>) -> Self {
+        self.env.push((key.into(), path.into()));
+        self
+    }
+
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    // 环境变量前缀（转为大写，空字符串表示不加前缀）
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        let prefix = prefix.trim().trim_end_matches('_').to_uppercase();
+        self.prefix = (!prefix.is_empty()).then_some(prefix);
+        self
+    }
+
+    pub fn env_paths(mut self, style: PathStyle) -> Self {
+        self.env_paths = style;
+        self
+    }
+
+    pub fn env_format(mut self, format: EnvFormat) -> Self {
+        self.env_formats.push(format);
+        self
+    }
+
+    pub fn helper(mut self, helper: Helper) -> Self {
+        self.helpers.push(helper);
+        self
+    }
+
+    pub fn stack(mut self, stack: Option<Stack>) -> Self {
+        self.stack = stack;
+        self
+    }
+
+    pub fn gitkeep(mut self, gitkeep: bool) -> Self {
+        self.gitkeep = gitkeep;
+        self
+    }
+
+    pub fn dir_readmes(mut self, dir_readmes: bool) -> Self {
+        self.dir_readmes = dir_readmes;
+        self
+    }
+
+    // 许可证（见 license::find）与版权持有人
+    pub fn license(mut self, license: Option<&'static License>, holder: Option<String>) -> Self {
+        self.license = license;
+        self.holder = holder;
+        self
+    }
+
+    pub fn author(mut self, name: Option<String>, email: Option<String>) -> Self {
+        self.author = name;
+        self.author_email = email;
+        self
+    }
+
+    // 模板问题的答案
+    pub fn var(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.vars.insert(name.into(), value.into());
+        self
+    }
+
+    // 是否 git init，以及是否创建初始提交
+    pub fn git(mut self, init: bool, commit: bool) -> Self {
+        self.git = init;
+        self.commit = init && commit;
+        self
+    }
+
+    pub fn build(self) -> Result<ProjectSpec> {
+        let mut template = match self.template {
+            Some(template) => template,
+            None => Template::load("default")?,
+        };
+        if let Some(prefix) = &self.prefix && !validate_prefix(prefix) {
+            return Err(
+                YuuskelError::Validation(
+                    self.language.msg(MsgKey::InvalidPrefix).replace("{}", &format!("'{}'", prefix))
+                )
+            );
+        }
+        let mut paths = self.dirs.iter().chain(self.env.iter().map(|(_, path)| path));
+        if let Some(path) = paths.find(|path| !template::is_relative(path)) {
+            return Err(
+                YuuskelError::Validation(
+                    self.language.msg(MsgKey::PathOutsideProject).replace("{}", &format!("'{}'", path))
+                )
+            );
+        }
+        for dir in &self.dirs {
+            template.add_dir(dir);
+        }
+        for (key, path) in &self.env {
+            template.add_env(key, path);
+        }
+        let mut env_formats = self.env_formats;
+        env_formats.sort();
+        env_formats.dedup();
+        let mut helpers = self.helpers;
+        helpers.sort();
+        helpers.dedup();
+        Ok(ProjectSpec {
+            target_dir: self.target_dir,
+            template,
+            language: self.language,
+            prefix: self.prefix,
+            env_paths: self.env_paths,
+            env_formats,
+            helpers,
+            stack: self.stack,
+            gitkeep: self.gitkeep,
+            dir_readmes: self.dir_readmes,
+            license: self.license,
+            holder: self.holder,
+            author: self.author,
+            author_email: self.author_email,
+            vars: self.vars,
+            git: self.git,
+            commit: self.commit,
+        })
+    }
+}
+
+// 执行结果（路径均相对项目根目录）
+#[derive(Default)]
+pub struct Report {
+    pub root: PathBuf,
+    // 新建的目录与文件
+    pub created: Vec<String>,
+    // 被修改的已有文件
+    pub updated: Vec<String>,
+    // 已存在而保持不变的文件
+    pub skipped: Vec<String>,
+    // .env 中 managed 变量的变化
+    pub env_changes: Vec<EnvChange>,
+    // 不影响结果的 git 失败
+    pub warnings: Vec<String>,
+}
+
+impl Report {
+    fn record(&mut self, action: &Action, outcome: &Outcome) {
+        if let Outcome::Failed(e) = outcome {
+            self.warnings.push(e.to_string());
+            return;
+        }
+        match action {
+            Action::CreateDir(dir) => self.created.push(dir.clone()),
+            Action::Write(file) if file.existed => self.updated.push(file.path.clone()),
+            Action::Write(file) => self.created.push(file.path.clone()),
+            Action::UpdateEnv { file, changes } => {
+                if !file.existed {
+                    self.created.push(file.path.clone());
+                } else if !changes.is_empty() {
+                    self.updated.push(file.path.clone());
+                }
+                self.env_changes.extend(changes.iter().cloned());
+            }
+            Action::Skip(path) => self.skipped.push(path.clone()),
+            Action::WriteMetadata { existed, .. } => {
+                let path = metadata::FILE_NAME.to_string();
+                if *existed { self.updated.push(path) } else { self.created.push(path) }
+            }
+            Action::CreateRoot | Action::GitInit | Action::GitAdd | Action::GitCommit => {}
+        }
+    }
+}
+
+impl ProjectSpec {
+    // 生成计划（只读取现状，不写磁盘）；目录中已有 yuuskel.toml 时按增量更新处理
+    pub fn plan(&self) -> Result<Plan> {
+        let target_dir = &self.target_dir;
+        let lang = self.language.code();
+        let recorded = Metadata::load(target_dir)?;
+        // 更改前缀会在 .env 与已生成的文件中留下旧的变量名，已有项目只能沿用记录的前缀
+        if let Some(metadata) = &recorded && metadata.prefix != self.prefix {
+            let show = |prefix: &Option<String>| format!("\"{}\"", prefix.as_deref().unwrap_or_default());
+            return Err(
+                YuuskelError::Validation(
+                    self.language
+                        .msg(MsgKey::PrefixLocked)
+                        .replacen("{}", &show(&metadata.prefix), 1)
+                        .replacen("{}", &show(&self.prefix), 1)
+                )
+            );
+        }
+        let prefix = self.prefix
+            .as_deref()
+            .map(|p| format!("{}_", p))
+            .unwrap_or_default();
+        let abs_str = generate::project_root(target_dir);
+
+        // 所有生成文件共用的渲染上下文
+        let mut vars = generate::base_vars(&abs_str, &prefix);
+        vars.insert("author".to_string(), self.author.clone().unwrap_or_default());
+        vars.insert("author_email".to_string(), self.author_email.clone().unwrap_or_default());
+        vars.extend(self.vars.clone());
+        generate::answer_vars(&mut vars, lang, self.license, self.holder.as_deref(), self.stack);
+        let ctx = Context::new(&self.template, lang, &prefix, vars, &self.helpers);
+
+        let mut plan = Plan::new(target_dir.clone());
+        if !target_dir.exists() {
+            plan.push(Action::CreateRoot);
+        }
+        let dirs = self.template.dir_paths();
+        generate::plan_dirs(&mut plan, &dirs);
+        // 占位文件：.gitkeep 与目录说明 README.md
+        let mut keep = if self.gitkeep { generate::gitkeep_files(&dirs) } else { Vec::new() };
+        generate::plan_placeholders(&mut plan, &keep);
+        if self.dir_readmes {
+            keep.extend(generate::plan_dir_readmes(&mut plan, lang, &ctx)?);
+        }
+        let env = &self.template.env;
+        generate::plan_env(&mut plan, &prefix, env, &abs_str, self.env_paths);
+        generate::plan_env_formats(&mut plan, &self.env_formats, &prefix, env, &abs_str, self.env_paths);
+        generate::plan_helpers(&mut plan, &self.helpers, &ctx)?;
+        generate::plan_files(&mut plan, &self.template, lang, &ctx)?;
+        if let Some(stack) = self.stack {
+            generate::plan_stack(&mut plan, stack, lang, &ctx)?;
+        }
+        generate::plan_gitignore(&mut plan, &self.template, self.stack, &keep);
+
+        if let Some(license) = self.license {
+            if target_dir.join("LICENSE").exists() {
+                plan.push(Action::Skip("LICENSE".to_string()));
+            } else {
+                let year = chrono::Local::now().year();
+                plan.push(
+                    Action::Write(FileWrite {
+                        path: "LICENSE".to_string(),
+                        content: license.render(year, self.holder.as_deref().unwrap_or_default()),
+                        existed: false,
+                    })
+                );
+            }
+        }
+
+        if self.git {
+            plan.push(Action::GitInit);
+        }
+
+        let license = self.license.map(|l| l.spdx.to_string());
+        // 记录元数据到 yuuskel.toml（首次创建；已有记录时更新本次的答案，语言、模板与前缀保持不变）
+        match recorded {
+            Some(mut metadata) if
+                metadata.stack != self.stack ||
+                metadata.gitkeep != self.gitkeep ||
+                metadata.dir_readmes != self.dir_readmes ||
+                metadata.env_paths != self.env_paths ||
+                metadata.env_formats != self.env_formats ||
+                metadata.helpers != self.helpers ||
+                metadata.license != license ||
+                metadata.holder != self.holder ||
+                metadata.dirs != dirs ||
+                metadata.env != *env ||
+                metadata.vars != self.vars
+            => {
+                metadata.stack = self.stack;
+                metadata.gitkeep = self.gitkeep;
+                metadata.dir_readmes = self.dir_readmes;
+                metadata.env_paths = self.env_paths;
+                metadata.env_formats = self.env_formats.clone();
+                metadata.helpers = self.helpers.clone();
+                metadata.license = license;
+                metadata.holder = self.holder.clone();
+                metadata.dirs = dirs;
+                metadata.env = env.clone();
+                metadata.vars = self.vars.clone();
+                plan.push(Action::WriteMetadata { metadata: Box::new(metadata), existed: true });
+            }
+            Some(_) => {}
+            None => {
+                plan.push(Action::WriteMetadata {
+                    metadata: Box::new(Metadata {
+                        version: metadata::current_version().to_string(),
+                        language: lang.to_string(),
+                        template: self.template.origin(),
+                        template_commit: self.template.commit().map(str::to_string),
+                        prefix: self.prefix.clone(),
+                        env_paths: self.env_paths,
+                        stack: self.stack,
+                        env_formats: self.env_formats.clone(),
+                        helpers: self.helpers.clone(),
+                        gitkeep: self.gitkeep,
+                        dir_readmes: self.dir_readmes,
+                        git_initialized: self.git,
+                        license,
+                        holder: self.holder.clone(),
+                        dirs,
+                        env: env.clone(),
+                        vars: self.vars.clone(),
+                    }),
+                    existed: false,
+                });
+            }
+        }
+
+        // 初始提交只在本次新建的仓库中进行：已有仓库中 git add . 会带上用户未提交的改动，回滚时提交也无法撤销
+        if self.commit && !target_dir.join(".git").exists() {
+            plan.push(Action::GitAdd);
+            plan.push(Action::GitCommit);
+        }
+        Ok(plan)
+    }
+}
+
+// 执行计划并汇总结果；每完成一个动作调用一次 progress。失败或被中断时已回滚
+pub fn apply(
+    plan: &Plan,
+    interrupt: &Interrupt,
+    mut progress: impl FnMut(&Action, &Outcome)
+) -> std::result::Result<Report, Rollback> {
+    let mut report = Report { root: plan.root.clone(), ..Report::default() };
+    plan.apply(interrupt, |action, outcome| {
+        report.record(action, outcome);
+        progress(action, outcome);
+    })?;
+    Ok(report)
+}
+
+// 规划并执行
+pub fn generate(spec: &ProjectSpec) -> Result<Report> {
+    let plan = spec.plan()?;
+    apply(&plan, &Interrupt::new(), |_, _| {}).map_err(|rollback| rollback.error)
+}
+
+// 升级已有项目时额外合并的选项（命令行中来自用户配置）
+#[derive(Default)]
+pub struct UpgradeOptions {
+    pub extra_dirs: Vec<String>,
+    pub env_formats: Vec<EnvFormat>,
+    pub helpers: Vec<Helper>,
+    pub gitkeep: bool,
+    pub dir_readmes: bool,
+    pub author: Option<String>,
+    pub author_email: Option<String>,
+}
+
+// 升级已有项目的计划：沿用 yuuskel.toml 中的答案，按记录的提交加载模板，补齐新增的结构并更新记录
+pub fn plan_upgrade(target_dir: &Path, mut metadata: Metadata, options: &UpgradeOptions) -> Result<Plan> {
+    let mut template = Template::load_pinned(&metadata.template, metadata.template_commit.as_deref())?;
+    let abs_str = generate::project_root(target_dir);
+
+    // 目录：已记录的 + 模板新增的 + 额外目录
+    let mut dirs = metadata.dirs.clone();
+    for d in template.dir_paths().iter().chain(&options.extra_dirs) {
+        if !dirs.contains(d) {
+            dirs.push(d.clone());
+        }
+    }
+
+    // .env：沿用记录的前缀，只改动 managed 变量
+    let prefix = metadata.prefix
+        .as_deref()
+        .map(|p| format!("{}_", p))
+        .unwrap_or_default();
+
+    // 模板新增的问题使用默认答案
+    for prompt in &template.prompts {
+        metadata.vars.entry(prompt.name.clone()).or_insert_with(|| prompt.default.clone());
+    }
+    for helper in &options.helpers {
+        if !metadata.helpers.contains(helper) {
+            metadata.helpers.push(*helper);
+        }
+    }
+    metadata.helpers.sort();
+    for format in &options.env_formats {
+        if !metadata.env_formats.contains(format) {
+            metadata.env_formats.push(*format);
+        }
+    }
+    metadata.env_formats.sort();
+    metadata.gitkeep |= options.gitkeep;
+    metadata.dir_readmes |= options.dir_readmes;
+
+    // 渲染上下文：目录包含已记录的与额外目录
+    for d in &dirs {
+        template.add_dir(d);
+    }
+    let lang = metadata.language.clone();
+    let mut vars = generate::base_vars(&abs_str, &prefix);
+    vars.insert("author".to_string(), options.author.clone().unwrap_or_default());
+    vars.insert("author_email".to_string(), options.author_email.clone().unwrap_or_default());
+    vars.extend(metadata.vars.clone());
+    let license = metadata.license.as_deref().and_then(crate::license::find);
+    generate::answer_vars(&mut vars, &lang, license, metadata.holder.as_deref(), metadata.stack);
+    let ctx = Context::new(&template, &lang, &prefix, vars, &metadata.helpers);
+
+    let mut plan = Plan::new(target_dir.to_path_buf());
+    generate::plan_dirs(&mut plan, &dirs);
+    let mut keep = if metadata.gitkeep { generate::gitkeep_files(&dirs) } else { Vec::new() };
+    generate::plan_placeholders(&mut plan, &keep);
+    if metadata.dir_readmes {
+        keep.extend(generate::plan_dir_readmes(&mut plan, &lang, &ctx)?);
+    }
+    generate::plan_env(&mut plan, &prefix, &template.env, &abs_str, metadata.env_paths);
+    generate::plan_env_formats(&mut plan, &metadata.env_formats, &prefix, &template.env, &abs_str, metadata.env_paths);
+    generate::plan_helpers(&mut plan, &metadata.helpers, &ctx)?;
+    generate::plan_files(&mut plan, &template, &lang, &ctx)?;
+    if let Some(stack) = metadata.stack {
+        generate::plan_stack(&mut plan, stack, &lang, &ctx)?;
+    }
+    generate::plan_gitignore(&mut plan, &template, metadata.stack, &keep);
+
+    // 更新记录的版本、目录与受管理的变量
+    metadata.version = metadata::current_version().to_string();
+    metadata.dirs = dirs;
+    metadata.env = template.env.clone();
+    plan.push(Action::WriteMetadata { metadata: Box::new(metadata), existed: true });
+    Ok(plan)
+}
+
+// 移动项目后的计划：按当前位置重写 .env 与其他环境文件中记录的变量；给出 style 时改用该路径形式并更新记录
+pub fn plan_relocate(target_dir: &Path, mut metadata: Metadata, style: Option<PathStyle>) -> Result<Plan> {
+    let env = metadata.env_vars()?;
+    let abs_str = generate::project_root(target_dir);
+    let prefix = metadata.prefix
+        .as_deref()
+        .map(|p| format!("{}_", p))
+        .unwrap_or_default();
+    let style = style.unwrap_or(metadata.env_paths);
+
+    let mut plan = Plan::new(target_dir.to_path_buf());
+    generate::plan_env(&mut plan, &prefix, &env, &abs_str, style);
+    generate::plan_env_formats(&mut plan, &metadata.env_formats, &prefix, &env, &abs_str, style);
+    if style != metadata.env_paths {
+        metadata.env_paths = style;
+        plan.push(Action::WriteMetadata { metadata: Box::new(metadata), existed: true });
+    }
+    Ok(plan)
+}
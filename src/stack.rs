@@ -0,0 +1,149 @@
+// 技术栈预设：在通用结构之上追加语言清单、.gitignore 片段与 USAGE.md 章节
+use serde::{ Deserialize, Serialize };
+
+use crate::i18n;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum Stack {
+    Python,
+    Rust,
+    Julia,
+    R,
+    Node,
+}
+
+struct Preset {
+    label: &'static str,
+    // (目标路径, 内容)，内容中的 {{...}} 按模板变量渲染
+    files: &'static [(&'static str, &'static str)],
+    // 追加的 .gitignore 片段
+    gitignore: &'static [&'static str],
+    // USAGE.md 中追加的章节：(语言代码, 内容)
+    usage: &'static [(&'static str, &'static str)],
+}
+
+const PYTHON: Preset = Preset {
+    label: "Python (pyproject.toml)",
+    files: &[
+        ("src/pyproject.toml", include_str!("templates/stacks/python/pyproject.toml")),
+    ],
+    gitignore: &["python", "jupyter"],
+    usage: &[
+        ("en", include_str!("templates/stacks/python/usage.en.md")),
+        ("zh", include_str!("templates/stacks/python/usage.zh.md")),
+        ("zh-Hant", include_str!("templates/stacks/python/usage.zh-Hant.md")),
+        ("ja", include_str!("templates/stacks/python/usage.ja.md")),
+    ],
+};
+
+const RUST: Preset = Preset {
+    label: "Rust (Cargo.toml)",
+    files: &[
+        ("src/Cargo.toml", include_str!("templates/stacks/rust/Cargo.toml")),
+        ("src/lib.rs", include_str!("templates/stacks/rust/lib.rs")),
+    ],
+    gitignore: &["rust"],
+    usage: &[
+        ("en", include_str!("templates/stacks/rust/usage.en.md")),
+        ("zh", include_str!("templates/stacks/rust/usage.zh.md")),
+        ("zh-Hant", include_str!("templates/stacks/rust/usage.zh-Hant.md")),
+        ("ja", include_str!("templates/stacks/rust/usage.ja.md")),
+    ],
+};
+
+const JULIA: Preset = Preset {
+    label: "Julia (Project.toml)",
+    files: &[
+        ("src/Project.toml", include_str!("templates/stacks/julia/Project.toml")),
+    ],
+    gitignore: &["julia"],
+    usage: &[
+        ("en", include_str!("templates/stacks/julia/usage.en.md")),
+        ("zh", include_str!("templates/stacks/julia/usage.zh.md")),
+        ("zh-Hant", include_str!("templates/stacks/julia/usage.zh-Hant.md")),
+        ("ja", include_str!("templates/stacks/julia/usage.ja.md")),
+    ],
+};
+
+const R: Preset = Preset {
+    label: "R (renv + DESCRIPTION)",
+    files: &[
+        ("src/DESCRIPTION", include_str!("templates/stacks/r/DESCRIPTION")),
+    ],
+    gitignore: &["r"],
+    usage: &[
+        ("en", include_str!("templates/stacks/r/usage.en.md")),
+        ("zh", include_str!("templates/stacks/r/usage.zh.md")),
+        ("zh-Hant", include_str!("templates/stacks/r/usage.zh-Hant.md")),
+        ("ja", include_str!("templates/stacks/r/usage.ja.md")),
+    ],
+};
+
+const NODE: Preset = Preset {
+    label: "Node.js (package.json)",
+    files: &[
+        ("src/package.json", include_str!("templates/stacks/node/package.json")),
+    ],
+    gitignore: &["node"],
+    usage: &[
+        ("en", include_str!("templates/stacks/node/usage.en.md")),
+        ("zh", include_str!("templates/stacks/node/usage.zh.md")),
+        ("zh-Hant", include_str!("templates/stacks/node/usage.zh-Hant.md")),
+        ("ja", include_str!("templates/stacks/node/usage.ja.md")),
+    ],
+};
+
+impl Stack {
+    pub fn all() -> [Stack; 5] {
+        [Stack::Python, Stack::Rust, Stack::Julia, Stack::R, Stack::Node]
+    }
+
+    fn preset(self) -> &'static Preset {
+        match self {
+            Stack::Python => &PYTHON,
+            Stack::Rust => &RUST,
+            Stack::Julia => &JULIA,
+            Stack::R => &R,
+            Stack::Node => &NODE,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Stack::Python => "python",
+            Stack::Rust => "rust",
+            Stack::Julia => "julia",
+            Stack::R => "r",
+            Stack::Node => "node",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        self.preset().label
+    }
+
+    pub fn files(self) -> &'static [(&'static str, &'static str)] {
+        self.preset().files
+    }
+
+    pub fn gitignore(self) -> &'static [&'static str] {
+        self.preset().gitignore
+    }
+
+    // USAGE.md 中追加的章节（按 zh-Hant → zh → en 回退）
+    pub fn usage(self, lang: &str) -> &'static str {
+        i18n::localized(lang, self.preset().usage)
+    }
+}
+
+// 清单中使用的包名：小写，非字母数字替换为 -
+pub fn package_name(project_name: &str) -> String {
+    let name: String = project_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let name = name.trim_matches('-');
+    if name.is_empty() { "project".to_string() } else { name.to_string() }
+}
@@ -0,0 +1,590 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{ Component, Path, PathBuf };
+
+use serde::{ Deserialize, Serialize };
+
+use crate::error::{ Result, YuuskelError };
+use crate::gitignore;
+use crate::helpers::Helper;
+use crate::i18n;
+use crate::remote;
+
+// 模板描述文件（template.toml）；序列化结果即 `yuuskel template show --resolved` 的输出
+#[derive(Deserialize, Serialize)]
+pub struct Template {
+    pub name: String,
+    // 继承的模板：内置模板名、目录（相对于本模板目录）或 git+<URL>
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default)]
+    pub dirs: Vec<Dir>,
+    // 从继承的模板中删除的目录（连同子目录及指向它们的变量）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_dirs: Vec<String>,
+    #[serde(default)]
+    pub env: Vec<EnvVar>,
+    // 重命名继承的变量：旧名 → 新名
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rename_env: BTreeMap<String, String>,
+    // .gitignore 片段名（见 gitignore.rs）
+    #[serde(default)]
+    pub gitignore: Vec<String>,
+    #[serde(default)]
+    pub files: Vec<FileSpec>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prompts: Vec<Prompt>,
+    // 被重命名的变量：旧名 → 现在的名字（文件中仍可用旧名引用 env_keys）
+    #[serde(skip)]
+    env_aliases: BTreeMap<String, String>,
+    // 继承链上的各个模板，最后一个是本模板
+    #[serde(skip)]
+    layers: Vec<Source>,
+}
+
+// 目录：可以只写路径，也可以附带说明（写入目录中的 README.md）
+#[derive(Deserialize, Serialize)]
+#[serde(from = "DirDef")]
+pub struct Dir {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Text>,
+    // 内容不纳入版本控制（在 .gitignore 受管区块中忽略）
+    #[serde(skip_serializing_if = "is_false")]
+    pub ignore: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DirDef {
+    Path(String),
+    Described {
+        path: String,
+        #[serde(default)]
+        description: Option<Text>,
+        #[serde(default)]
+        ignore: bool,
+    },
+}
+
+impl From<DirDef> for Dir {
+    fn from(def: DirDef) -> Dir {
+        match def {
+            DirDef::Path(path) => Dir { path, description: None, ignore: false },
+            DirDef::Described { path, description, ignore } => Dir { path, description, ignore },
+        }
+    }
+}
+
+
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+pub struct EnvVar {
+    pub key: String,
+    pub path: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct FileSpec {
+    // 目标路径（相对项目根目录）
+    pub path: String,
+    // 源文件（相对模板的 files/ 目录）
+    pub source: String,
+    // 是否按模板语法渲染（{{ 变量 }}、{% if %}、{% for %}）
+    #[serde(default = "default_true")]
+    pub render: bool,
+    // 声明该文件的模板（继承时 source 相对于它的 files/ 目录）
+    #[serde(skip_deserializing)]
+    pub from: String,
+    #[serde(skip)]
+    layer: usize,
+}
+
+// 模板自定义问题，答案可在文件中以 {{name}} 引用
+#[derive(Deserialize, Serialize)]
+pub struct Prompt {
+    pub name: String,
+    pub message: Text,
+    #[serde(default)]
+    pub default: String,
+}
+
+// 单一文本或按语言代码区分的文本
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Text {
+    Plain(String),
+    Localized(BTreeMap<String, String>),
+}
+
+impl Text {
+    pub fn get(&self, lang: &str) -> &str {
+        match self {
+            Text::Plain(text) => text,
+            Text::Localized(texts) =>
+                i18n::fallbacks(lang)
+                    .into_iter()
+                    .find_map(|lang| texts.get(lang))
+                    .or_else(|| texts.values().next())
+                    .map(String::as_str)
+                    .unwrap_or_default(),
+        }
+    }
+}
+
+enum Source {
+    Builtin(&'static str),
+    Dir(PathBuf),
+    // git+<URL> 来源：缓存中的克隆及检出的提交
+    Git {
+        source: String,
+        commit: String,
+        dir: PathBuf,
+    },
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+// 内置模板（编译进二进制）
+struct Builtin {
+    name: &'static str,
+    manifest: &'static str,
+    // files/ 下的文件：(相对路径, 内容)
+    files: &'static [(&'static str, &'static str)],
+}
+
+const BUILTIN: &[Builtin] = &[
+    Builtin {
+        name: "default",
+        manifest: include_str!("templates/default/template.toml"),
+        files: &[
+            ("usage.en.md", include_str!("templates/default/files/usage.en.md")),
+            ("usage.zh.md", include_str!("templates/default/files/usage.zh.md")),
+            ("usage.zh-Hant.md", include_str!("templates/default/files/usage.zh-Hant.md")),
+            ("usage.ja.md", include_str!("templates/default/files/usage.ja.md")),
+            ("readme.en.md", include_str!("templates/default/files/readme.en.md")),
+            ("readme.zh.md", include_str!("templates/default/files/readme.zh.md")),
+            ("readme.zh-Hant.md", include_str!("templates/default/files/readme.zh-Hant.md")),
+            ("readme.ja.md", include_str!("templates/default/files/readme.ja.md")),
+        ],
+    },
+];
+
+impl Template {
+    // 按名称加载内置模板，或从目录（目录中需有 template.toml）、git+<URL> 加载
+    pub fn load(name_or_path: &str) -> Result<Template> {
+        Template::load_pinned(name_or_path, None)
+    }
+
+    // 同 load；git+<URL> 来源检出 yuuskel.toml 中固定的提交
+    pub fn load_pinned(name_or_path: &str, commit: Option<&str>) -> Result<Template> {
+        load_chain(name_or_path, commit, &mut Vec::new())
+    }
+
+    // 内置模板名
+    pub fn builtin_names() -> Vec<&'static str> {
+        BUILTIN.iter()
+            .map(|b| b.name)
+            .collect()
+    }
+
+    pub fn dir_paths(&self) -> Vec<String> {
+        self.dirs
+            .iter()
+            .map(|d| d.path.clone())
+            .collect()
+    }
+
+    // 追加模板之外的目录（已存在则忽略）
+    pub fn add_dir(&mut self, path: &str) {
+        if !self.dirs.iter().any(|d| d.path == path) {
+            self.dirs.push(Dir { path: path.to_string(), description: None, ignore: false });
+        }
+    }
+
+    // 追加模板之外的路径变量（同名变量已存在则忽略）
+    pub fn add_env(&mut self, key: &str, path: &str) {
+        if !self.env.iter().any(|v| v.key == key) {
+            self.env.push(EnvVar { key: key.to_string(), path: path.to_string() });
+        }
+    }
+
+    // 记录到 yuuskel.toml 的模板来源：内置模板名、模板目录的绝对路径或 git+<URL>
+    pub fn origin(&self) -> String {
+        self.layers.last().map_or_else(|| self.name.clone(), Source::origin)
+    }
+
+    // git+<URL> 来源检出的提交（记录到 yuuskel.toml 以固定模板版本）
+    pub fn commit(&self) -> Option<&str> {
+        match self.layers.last() {
+            Some(Source::Git { commit, .. }) => Some(commit),
+            _ => None,
+        }
+    }
+
+    // 继承链上各模板的来源，从最底层的模板开始
+    pub fn chain(&self) -> Vec<String> {
+        self.layers.iter().map(Source::origin).collect()
+    }
+
+    // 本模板的 template.toml 原文（不含继承的内容）
+    pub fn manifest(&self) -> Result<String> {
+        match self.layers.last() {
+            Some(Source::Builtin(name)) =>
+                Ok(
+                    BUILTIN.iter()
+                        .find(|b| b.name == *name)
+                        .map(|b| b.manifest.to_string())
+                        .unwrap_or_default()
+                ),
+            Some(Source::Dir(dir) | Source::Git { dir, .. }) => {
+                let path = dir.join("template.toml");
+                fs::read_to_string(&path).map_err(|e| YuuskelError::fs(&path, e))
+            }
+            None => Ok(String::new()),
+        }
+    }
+
+    // 读取文件的源文件（从声明它的模板中）；含 {lang} 时优先当前语言，缺失则按 zh-Hant → zh → en 回退
+    pub fn read_file(&self, file: &FileSpec, lang: &str) -> Result<String> {
+        let source = &file.source;
+        let candidates = if source.contains("{lang}") {
+            i18n::fallbacks(lang)
+                .into_iter()
+                .map(|lang| source.replace("{lang}", lang))
+                .collect()
+        } else {
+            vec![source.to_string()]
+        };
+        if let Some(layer) = self.layers.get(file.layer) {
+            for candidate in &candidates {
+                if let Some(content) = layer.read_raw(candidate)? {
+                    return Ok(content);
+                }
+            }
+        }
+        Err(YuuskelError::template(&file.from, format!("no file '{}'", candidates[0])))
+    }
+
+    // 把子模板叠加到本模板（已解析的父模板）上。顺序固定：
+    // 删除目录 → 追加或更新目录 → 重命名变量 → 追加或更新变量 → 追加 .gitignore 片段 → 按目标路径替换或追加文件 → 按名称替换或追加问题
+    fn overlay(mut self, child: Template) -> Result<Template> {
+        let origin = child.origin();
+        for removed in &child.remove_dirs {
+            let removed = removed.trim_end_matches('/');
+            let under = |path: &str| path == removed || path.starts_with(&format!("{}/", removed));
+            if !self.dirs.iter().any(|d| under(&d.path)) {
+                return Err(YuuskelError::template(&origin, format!("remove_dirs: '{}' is not an inherited directory", removed)));
+            }
+            self.dirs.retain(|d| !under(&d.path));
+            self.env.retain(|v| !under(&v.path));
+        }
+        for dir in child.dirs {
+            match self.dirs.iter_mut().find(|d| d.path == dir.path) {
+                // 重复列出的目录保持原位置，没有说明时沿用继承的说明与忽略设置
+                Some(existing) => {
+                    if dir.description.is_some() {
+                        existing.description = dir.description;
+                    }
+                    existing.ignore |= dir.ignore;
+                }
+                None => self.dirs.push(dir),
+            }
+        }
+
+        for (old, new) in &child.rename_env {
+            let Some(var) = self.env.iter_mut().find(|v| v.key == *old) else {
+                return Err(YuuskelError::template(&origin, format!("rename_env: no inherited variable '{}'", old)));
+            };
+            var.key = new.clone();
+            for target in self.env_aliases.values_mut() {
+                if target == old {
+                    *target = new.clone();
+                }
+            }
+            self.env_aliases.insert(old.clone(), new.clone());
+        }
+        for var in child.env {
+            match self.env.iter_mut().find(|v| v.key == var.key) {
+                Some(existing) => existing.path = var.path,
+                None => self.env.push(var),
+            }
+        }
+
+        for fragment in child.gitignore {
+            if !self.gitignore.contains(&fragment) {
+                self.gitignore.push(fragment);
+            }
+        }
+
+        let layer = self.layers.len();
+        for mut file in child.files {
+            file.layer = layer;
+            match self.files.iter_mut().find(|f| f.path == file.path) {
+                Some(existing) => *existing = file,
+                None => self.files.push(file),
+            }
+        }
+        for prompt in child.prompts {
+            match self.prompts.iter_mut().find(|p| p.name == prompt.name) {
+                Some(existing) => *existing = prompt,
+                None => self.prompts.push(prompt),
+            }
+        }
+
+        self.name = child.name;
+        if !child.description.is_empty() {
+            self.description = child.description;
+        }
+        self.layers.extend(child.layers);
+        Ok(self)
+    }
+}
+
+impl Source {
+    fn origin(&self) -> String {
+        match self {
+            Source::Builtin(name) => name.to_string(),
+            Source::Dir(dir) => dir.to_string_lossy().replace('\\', "/"),
+            Source::Git { source, .. } => source.clone(),
+        }
+    }
+
+    fn read_raw(&self, source: &str) -> Result<Option<String>> {
+        match self {
+            Source::Builtin(name) =>
+                Ok(
+                    BUILTIN.iter()
+                        .find(|b| b.name == *name)
+                        .and_then(|b| b.files.iter().find(|(f, _)| *f == source))
+                        .map(|(_, content)| content.to_string())
+                ),
+            Source::Dir(dir) | Source::Git { dir, .. } => {
+                let path = dir.join("files").join(source);
+                if !path.is_file() {
+                    return Ok(None);
+                }
+                fs::read_to_string(&path)
+                    .map(Some)
+                    .map_err(|e| YuuskelError::fs(&path, e))
+            }
+        }
+    }
+}
+
+// 加载模板，有 extends 时先解析父模板再叠加；chain 为继承链上已加载的来源（用于发现循环继承）
+fn load_chain(name_or_path: &str, commit: Option<&str>, chain: &mut Vec<String>) -> Result<Template> {
+    let template = load_one(name_or_path, commit)?;
+    let origin = template.origin();
+    if chain.contains(&origin) {
+        chain.push(origin.clone());
+        return Err(YuuskelError::template(&origin, format!("circular extends: {}", chain.join(" → "))));
+    }
+    chain.push(origin.clone());
+
+    let Some(parent) = template.extends.clone() else {
+        if !template.remove_dirs.is_empty() || !template.rename_env.is_empty() {
+            return Err(YuuskelError::template(&origin, "remove_dirs and rename_env require extends"));
+        }
+        return Ok(template);
+    };
+    // extends 中的相对路径相对于声明它的模板目录
+    let parent = match template.layers.last() {
+        Some(Source::Dir(dir) | Source::Git { dir, .. }) if dir.join(&parent).join("template.toml").is_file() =>
+            dir.join(&parent).to_string_lossy().to_string(),
+        _ => parent,
+    };
+    let mut resolved = load_chain(&parent, None, chain)?.overlay(template)?;
+    resolved.extends = None;
+    Ok(resolved)
+}
+
+// 加载单个模板（不解析 extends）
+fn load_one(name_or_path: &str, commit: Option<&str>) -> Result<Template> {
+    let (mut template, source) = if remote::is_remote(name_or_path) {
+        let cached = remote::fetch(name_or_path, commit)?;
+        let template = load_dir(&cached.dir, name_or_path)?;
+        (template, Source::Git { source: name_or_path.to_string(), commit: cached.commit, dir: cached.dir })
+    } else if Path::new(name_or_path).join("template.toml").is_file() {
+        let path = Path::new(name_or_path);
+        let template = load_dir(path, &path.join("template.toml").display().to_string())?;
+        (template, Source::Dir(path.canonicalize().map_err(|e| YuuskelError::fs(path, e))?))
+    } else {
+        match BUILTIN.iter().find(|b| b.name == name_or_path) {
+            Some(builtin) => (parse(builtin.manifest, builtin.name)?, Source::Builtin(builtin.name)),
+            None => {
+                let known = Template::builtin_names().join(", ");
+                return Err(YuuskelError::TemplateNotFound { name: name_or_path.to_string(), known });
+            }
+        }
+    };
+    let origin = source.origin();
+    for file in &mut template.files {
+        file.from = origin.clone();
+    }
+    template.layers = vec![source];
+    Ok(template)
+}
+
+// 读取目录中的 template.toml；origin 为错误消息中显示的来源
+fn load_dir(dir: &Path, origin: &str) -> Result<Template> {
+    let manifest = dir.join("template.toml");
+    if !manifest.is_file() {
+        return Err(YuuskelError::template(origin, "no template.toml"));
+    }
+    let content = fs::read_to_string(&manifest).map_err(|e| YuuskelError::fs(&manifest, e))?;
+    parse(&content, origin)
+}
+
+fn parse(content: &str, origin: &str) -> Result<Template> {
+    let template: Template = toml::from_str(content).map_err(|e| YuuskelError::template(origin, e))?;
+    if let Some(unknown) = template.gitignore.iter().find(|name| !gitignore::is_fragment(name)) {
+        return Err(
+            YuuskelError::template(
+                origin,
+                format!(
+                    "unknown gitignore fragment '{}' (available: {})",
+                    unknown,
+                    gitignore::fragment_names().join(", ")
+                )
+            )
+        );
+    }
+    // 路径都相对于项目根目录（source 相对于 files/），不能借助绝对路径或 .. 写到外面
+    let paths = template.dirs
+        .iter()
+        .map(|d| ("dirs", &d.path))
+        .chain(template.env.iter().map(|v| ("env", &v.path)))
+        .chain(template.files.iter().flat_map(|f| [("files.path", &f.path), ("files.source", &f.source)]));
+    for (field, path) in paths {
+        if !is_relative(path) {
+            return Err(
+                YuuskelError::template(
+                    origin,
+                    format!("{}: '{}' must be a relative path inside the project (no absolute paths or ..)", field, path)
+                )
+            );
+        }
+    }
+    Ok(template)
+}
+
+// 非空的相对路径，且不含 ..、根目录或盘符
+pub fn is_relative(path: &str) -> bool {
+    !path.is_empty() &&
+        Path::new(path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+// 渲染上下文：标量变量（内置变量与模板问题的答案）+ 目录、环境变量等列表
+#[derive(Serialize)]
+pub struct Context {
+    #[serde(flatten)]
+    pub vars: BTreeMap<String, String>,
+    pub dirs: Vec<DirContext>,
+    pub env: Vec<EnvContext>,
+    // 不带前缀的变量名 → 实际的变量名，如 OUTPUT_DIR → MYPROJ_OUTPUT_DIR
+    pub env_keys: BTreeMap<String, String>,
+    pub helpers: Vec<Helper>,
+}
+
+#[derive(Serialize)]
+pub struct DirContext {
+    pub path: String,
+    pub description: String,
+}
+
+#[derive(Serialize)]
+pub struct EnvContext {
+    // 带前缀的变量名
+    pub key: String,
+    // 模板中定义的变量名
+    pub name: String,
+    pub path: String,
+}
+
+impl Context {
+    pub fn new(
+        template: &Template,
+        lang: &str,
+        prefix: &str,
+        vars: BTreeMap<String, String>,
+        helpers: &[Helper]
+    ) -> Context {
+        let dirs = template.dirs
+            .iter()
+            .map(|d| DirContext {
+                path: d.path.clone(),
+                description: d.description
+                    .as_ref()
+                    .map(|t| t.get(lang).to_string())
+                    .unwrap_or_default(),
+            })
+            .collect();
+        let env: Vec<EnvContext> = template.env
+            .iter()
+            .map(|v| EnvContext {
+                key: format!("{}{}", prefix, v.key),
+                name: v.key.clone(),
+                path: v.path.clone(),
+            })
+            .collect();
+        let mut env_keys: BTreeMap<String, String> = env
+            .iter()
+            .map(|v| (v.name.clone(), v.key.clone()))
+            .collect();
+        // 继承的文件可能仍用旧名引用被重命名的变量
+        for (old, new) in &template.env_aliases {
+            if let Some(key) = env_keys.get(new).cloned() {
+                env_keys.entry(old.clone()).or_insert(key);
+            }
+        }
+        Context { vars, dirs, env, env_keys, helpers: helpers.to_vec() }
+    }
+}
+
+// 用 minijinja 渲染：支持 {{ 变量 }}、{% if %} 条件与 {% for %} 循环；
+// 块标签所在行不留空行，保留末尾换行
+pub fn render(name: &str, content: &str, ctx: impl Serialize) -> Result<String> {
+    let mut env = minijinja::Environment::new();
+    env.set_keep_trailing_newline(true);
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.template_from_named_str(name, content)
+        .and_then(|t| t.render(ctx))
+        .map_err(|e| YuuskelError::template(name, format!("{:#}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Language;
+
+    // 内置模板的文档与目录说明要覆盖界面支持的每种语言（BUILTIN 需手动列出文件）
+    #[test]
+    fn builtins_cover_every_language() {
+        for builtin in BUILTIN {
+            let template = Template::load(builtin.name).unwrap();
+            for (_, lang) in Language::all() {
+                let code = lang.code();
+                for file in template.files.iter().filter(|f| f.source.contains("{lang}")) {
+                    let source = file.source.replace("{lang}", code);
+                    assert!(builtin.files.iter().any(|(f, _)| *f == source), "{}: missing {}", builtin.name, source);
+                }
+                for dir in &template.dirs {
+                    if let Some(Text::Localized(texts)) = &dir.description {
+                        assert!(texts.contains_key(code), "{}: {} has no {} description", builtin.name, dir.path, code);
+                    }
+                }
+            }
+        }
+    }
+}
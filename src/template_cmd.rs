@@ -0,0 +1,112 @@
+// `yuuskel template`：查看模板（含解析继承后的结果），管理缓存中的远程模板（git+<URL>）
+use colored::*;
+
+use crate::cli::{ TemplateArgs, TemplateCommand };
+use crate::config::Config;
+use yuuskel::error::{ Result, YuuskelError };
+use yuuskel::remote::{ self, Cached };
+use yuuskel::template::Template;
+use yuuskel::{ Language, MsgKey };
+
+pub fn run(lang: Language, args: &TemplateArgs, config: &Config) -> Result<()> {
+    match &args.command {
+        TemplateCommand::List => list(lang),
+        TemplateCommand::Show { source, resolved } => {
+            let source = source.as_deref().or(config.template.as_deref()).unwrap_or("default");
+            show(source, *resolved)
+        }
+        TemplateCommand::Update { source } => update(lang, source.as_deref()),
+        TemplateCommand::Remove { source } => {
+            let cached = find(lang, source)?;
+            remote::remove(&cached)?;
+            println!("{}", lang.msg(MsgKey::TemplateRemoved).replace("{}", &display(&cached).cyan().to_string()));
+            Ok(())
+        }
+    }
+}
+
+fn list(lang: Language) -> Result<()> {
+    println!("{}", lang.msg(MsgKey::TemplateListBuiltin).bold());
+    for name in Template::builtin_names() {
+        let template = Template::load(name)?;
+        println!("  {} — {}", name.cyan(), template.description.dimmed());
+    }
+
+    let cache = remote::cache_dir().map(|dir| dir.display().to_string()).unwrap_or_default();
+    println!("\n{}", lang.msg(MsgKey::TemplateListCached).replace("{}", &cache).bold());
+    let cached = remote::cached()?;
+    if cached.is_empty() {
+        println!("{}", lang.msg(MsgKey::TemplateListEmpty).dimmed());
+    }
+    for cached in &cached {
+        // 缓存中的模板有误时仍然列出，便于删除
+        let name = Template::load(&cached.dir.to_string_lossy())
+            .map(|t| t.name)
+            .unwrap_or_default();
+        println!("  {}  {}  {}", display(cached).cyan(), remote::short(&cached.commit).yellow(), name);
+    }
+    Ok(())
+}
+
+// 原样输出 template.toml，或输出解析 extends 之后的结果（文件的 from 为声明它的模板）
+fn show(source: &str, resolved: bool) -> Result<()> {
+    let template = Template::load(source)?;
+    if !resolved {
+        print!("{}", template.manifest()?);
+        return Ok(());
+    }
+    let body = toml::to_string(&template).map_err(|e| YuuskelError::template(source, e))?;
+    println!("# resolved: {}", template.chain().join(" → "));
+    if let Some(commit) = template.commit() {
+        println!("# commit: {}", commit);
+    }
+    print!("\n{}", body);
+    Ok(())
+}
+
+fn update(lang: Language, source: Option<&str>) -> Result<()> {
+    let targets = match source {
+        // 尚未缓存的 git+<URL> 直接获取
+        Some(source) if remote::is_remote(source) && remote::find(source)?.is_none() => {
+            let cached = remote::fetch(source, None)?;
+            println!(
+                "{}",
+                lang
+                    .msg(MsgKey::TemplateFetched)
+                    .replacen("{}", &display(&cached).cyan().to_string(), 1)
+                    .replacen("{}", remote::short(&cached.commit), 1)
+            );
+            return Ok(());
+        }
+        Some(source) => vec![find(lang, source)?],
+        None => remote::cached()?,
+    };
+    for cached in targets {
+        let updated = remote::update(&cached)?;
+        let message = if updated.commit == cached.commit {
+            lang.msg(MsgKey::TemplateUpToDate)
+                .replacen("{}", &display(&cached), 1)
+                .replacen("{}", remote::short(&cached.commit), 1)
+                .green()
+                .to_string()
+        } else {
+            lang.msg(MsgKey::TemplateUpdated)
+                .replacen("{}", &display(&cached).cyan().to_string(), 1)
+                .replacen("{}", remote::short(&cached.commit), 1)
+                .replacen("{}", &remote::short(&updated.commit).yellow().to_string(), 1)
+        };
+        println!("{}", message);
+    }
+    Ok(())
+}
+
+fn find(lang: Language, source: &str) -> Result<Cached> {
+    remote::find(source)?.ok_or_else(|| {
+        YuuskelError::Validation(lang.msg(MsgKey::TemplateNotCached).replace("{}", source))
+    })
+}
+
+// 以 --template 中的写法显示
+fn display(cached: &Cached) -> String {
+    format!("git+{}", cached.url)
+}
@@ -0,0 +1,40 @@
+# Project paths generated by yuuskel from the template's env vars.
+#
+# Each path is read from the environment, then from the project's .env, and
+# falls back to the default layout relative to the project root.
+module Paths
+
+const _ROOT = dirname(@__DIR__)
+
+function _load_env(path)
+    values = Dict{String,String}()
+    isfile(path) || return values
+    for raw in eachline(path)
+        line = strip(raw)
+        (isempty(line) || startswith(line, "#") || !occursin('=', line)) && continue
+        key, value = strip.(split(line, '='; limit = 2))
+        key = strip(replace(key, r"^export\s+" => ""))
+        if length(value) >= 2 && value[1] == value[end] && value[1] in ('"', '\'')
+            value = value[2:end-1]
+        else
+            value = rstrip(first(split(value, " #"; limit = 2)))
+        end
+        values[String(key)] = String(value)
+    end
+    values
+end
+
+const _ENV = _load_env(joinpath(_ROOT, ".env"))
+
+function _get(key, default, root)
+    value = get(ENV, key, get(_ENV, key, ""))
+    isempty(value) && return normpath(joinpath(root, default))
+    value = replace(value, "\${PROJECT_ROOT}" => root, "\$PROJECT_ROOT" => root)
+    isabspath(value) ? value : normpath(joinpath(root, value))
+end
+
+const PROJECT_ROOT = rstrip(_get("PROJECT_ROOT", ".", _ROOT), '/')
+{% for var in env %}
+const {{ var.name }} = _get("{{ var.key }}", "{{ var.path }}", PROJECT_ROOT)
+{% endfor %}
+end
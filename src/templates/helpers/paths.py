@@ -0,0 +1,51 @@
+"""Project paths generated by yuuskel from the template's env vars.
+
+Each path is read from the environment, then from the project's .env, and
+falls back to the default layout relative to the project root.
+"""
+
+import os
+from pathlib import Path
+
+_ROOT = Path(__file__).resolve().parent.parent
+
+
+def _load_env(path):
+    values = {}
+    if not path.is_file():
+        return values
+    for line in path.read_text(encoding="utf-8").splitlines():
+        line = line.strip()
+        if not line or line.startswith("#") or "=" not in line:
+            continue
+        key, _, value = line.partition("=")
+        key = key.strip()
+        if key.startswith("export "):
+            key = key[len("export "):].strip()
+        value = value.strip()
+        if len(value) >= 2 and value[0] == value[-1] and value[0] in "\"'":
+            value = value[1:-1]
+        else:
+            value = value.split(" #", 1)[0].rstrip()
+        values[key] = value
+    return values
+
+
+_ENV = _load_env(_ROOT / ".env")
+
+
+def _resolve(value, root):
+    value = value.replace("${PROJECT_ROOT}", str(root)).replace("$PROJECT_ROOT", str(root))
+    path = Path(value)
+    return path if path.is_absolute() else (root / path).resolve()
+
+
+def _get(key, default, root):
+    value = os.environ.get(key) or _ENV.get(key)
+    return _resolve(value, root) if value else root / default
+
+
+PROJECT_ROOT = _get("PROJECT_ROOT", ".", _ROOT).resolve()
+{% for var in env %}
+{{ var.name }} = _get("{{ var.key }}", "{{ var.path }}", PROJECT_ROOT)
+{% endfor %}
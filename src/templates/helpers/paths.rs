@@ -0,0 +1,94 @@
+// Project paths generated by yuuskel from the template's env vars.
+//
+// Each path is read from the environment, then from the project's .env, and
+// falls back to the default layout relative to the project root (the nearest
+// directory containing yuuskel.toml or .env).
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+struct Paths {
+    root: PathBuf,
+    env: HashMap<String, String>,
+}
+
+fn paths() -> &'static Paths {
+    static PATHS: OnceLock<Paths> = OnceLock::new();
+    PATHS.get_or_init(|| {
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let base = cwd
+            .ancestors()
+            .find(|dir| dir.join("yuuskel.toml").is_file() || dir.join(".env").is_file())
+            .map_or_else(|| cwd.clone(), Path::to_path_buf);
+        let env = load_env(&base.join(".env"));
+        let root = match lookup(&env, "PROJECT_ROOT") {
+            Some(value) => resolve(&value, &base),
+            None => base,
+        };
+        Paths { root, env }
+    })
+}
+
+fn load_env(path: &Path) -> HashMap<String, String> {
+    let content = std::fs::read_to_string(path).unwrap_or_default();
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let (key, value) = line.split_once('=')?;
+            let key = key.trim();
+            let key = key.strip_prefix("export ").unwrap_or(key).trim();
+            let value = value.trim();
+            let quoted = value.len() >= 2
+                && (value.starts_with('"') && value.ends_with('"')
+                    || value.starts_with('\'') && value.ends_with('\''));
+            let value = if quoted {
+                &value[1..value.len() - 1]
+            } else {
+                value.split(" #").next().unwrap_or_default().trim_end()
+            };
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+fn lookup(env: &HashMap<String, String>, key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .filter(|v| !v.is_empty())
+        .or_else(|| env.get(key).cloned().filter(|v| !v.is_empty()))
+}
+
+fn resolve(value: &str, root: &Path) -> PathBuf {
+    let root_str = root.to_string_lossy();
+    let value = value
+        .replace("${PROJECT_ROOT}", &root_str)
+        .replace("$PROJECT_ROOT", &root_str);
+    let path = PathBuf::from(value);
+    // collecting components drops "." segments
+    let path = if path.is_absolute() { path } else { root.join(path) };
+    path.components().collect()
+}
+
+fn get(key: &str, default: &str) -> PathBuf {
+    let paths = paths();
+    match lookup(&paths.env, key) {
+        Some(value) => resolve(&value, &paths.root),
+        None => paths.root.join(default),
+    }
+}
+
+pub fn project_root() -> PathBuf {
+    paths().root.clone()
+}
+{% for var in env %}
+
+pub fn {{ var.name | lower }}() -> PathBuf {
+    get("{{ var.key }}", "{{ var.path }}")
+}
+{% endfor %}
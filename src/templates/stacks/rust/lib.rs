@@ -0,0 +1 @@
+// {{project_name}}: shared code for the binaries in scripts/ and the notebooks.
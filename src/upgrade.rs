@@ -0,0 +1,102 @@
+// `yuuskel upgrade`：读取 yuuskel.toml 复用之前的答案，补齐新版本新增的结构
+use colored::*;
+
+use crate::cli::UpgradeArgs;
+use crate::config::Config;
+use yuuskel::error::Result;
+use yuuskel::generate;
+use yuuskel::metadata::{ self, Metadata };
+use yuuskel::plan::{ Action, Interrupt };
+use crate::report;
+use yuuskel::skeleton::{ self, UpgradeOptions };
+use yuuskel::{ Language, MsgKey };
+
+pub fn run(lang: Language, args: &UpgradeArgs, config: &Config, interrupt: &Interrupt) -> Result<()> {
+    let target_dir = &args.dir;
+    let metadata = Metadata::load(target_dir)?.ok_or_else(|| {
+        metadata::not_found(lang, target_dir)
+    })?;
+    println!("{}{}", lang.msg(MsgKey::UpgradeTitle), generate::project_root(target_dir).cyan());
+
+    // 配置中的额外目录、辅助模块、环境文件与占位文件一并合并
+    let options = UpgradeOptions {
+        extra_dirs: config.extra_dirs.clone(),
+        env_formats: config.env_formats.clone(),
+        helpers: config.helpers.clone(),
+        gitkeep: config.gitkeep,
+        dir_readmes: config.dir_readmes,
+        author: config.author.name.clone(),
+        author_email: config.author.email.clone(),
+    };
+    let old_version = metadata.version.clone();
+    let version_changed = old_version != metadata::current_version();
+    let plan = skeleton::plan_upgrade(target_dir, metadata, &options)?;
+
+    // 变更摘要所需的计数
+    let mut created = 0;
+    let mut env_changed = 0;
+    let mut written = 0;
+    for action in &plan.actions {
+        match action {
+            Action::CreateDir(_) => {
+                created += 1;
+            }
+            Action::UpdateEnv { changes, .. } => {
+                env_changed += changes.len();
+            }
+            Action::Write(_) => {
+                written += 1;
+            }
+            _ => {}
+        }
+    }
+
+    if args.dry_run {
+        report::print_plan(lang, &plan);
+        return Ok(());
+    }
+
+    plan.apply(interrupt, |action, outcome| {
+        if let Action::UpdateEnv { changes, .. } = action {
+            // 仅在变量有变化时提示，并列出变化的键
+            if !changes.is_empty() {
+                let keys = changes
+                    .iter()
+                    .map(|c| c.key.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                println!("{}{} ({})", lang.msg(MsgKey::UpdateDotEnv), ".env".blue(), keys.dimmed());
+            }
+        } else if !matches!(action, Action::Skip(_)) {
+            report::print_step(lang, true, action, outcome);
+        }
+    }).map_err(|rollback| {
+        report::print_rollback(lang, &rollback.steps);
+        rollback.error
+    })?;
+
+    if version_changed {
+        println!(
+            "{}",
+            lang
+                .msg(MsgKey::UpgradeVersion)
+                .replacen("{}", &old_version, 1)
+                .replacen("{}", metadata::current_version(), 1)
+        );
+    }
+    if created == 0 && env_changed == 0 && written == 0 && !version_changed {
+        println!("\n{}", lang.msg(MsgKey::UpToDate).green().bold());
+    } else {
+        println!(
+            "\n{}",
+            lang
+                .msg(MsgKey::UpgradeDone)
+                .replacen("{}", &created.to_string(), 1)
+                .replacen("{}", &env_changed.to_string(), 1)
+                .replacen("{}", &written.to_string(), 1)
+                .green()
+                .bold()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,98 @@
+// 答案文件：--save-answers 保存的答案可以用 --answers 原样重放
+#![cfg(unix)]
+
+mod common;
+
+use std::fs;
+
+use common::{ assert_success, tree, Sandbox };
+
+#[test]
+fn save_and_replay() {
+    let sandbox = Sandbox::new();
+    let output = sandbox.run(
+        &[
+            "--name",
+            "first",
+            "--prefix",
+            "lab",
+            "--stack",
+            "python",
+            "--git",
+            "--no-commit",
+            "--license",
+            "MIT",
+            "--gitkeep",
+            "--dir-readmes",
+            "--env-paths",
+            "relative",
+            "--env-format",
+            "sh,json",
+            "--helper",
+            "python",
+            "--yes",
+            "--save-answers",
+            "answers.toml",
+        ]
+    );
+    assert_success(&output);
+    let saved = fs::read_to_string(sandbox.work().join("answers.toml")).unwrap();
+    insta::assert_snapshot!("saved_answers", saved);
+
+    // 重放时不给任何参数、也没有 --yes：所有问题都必须由答案文件回答
+    fs::rename(sandbox.work().join("first"), sandbox.work().join("original")).unwrap();
+    let output = sandbox.run(&["--answers", "answers.toml"]);
+    assert_success(&output);
+
+    let original = sandbox.work().join("original");
+    let replayed = sandbox.work().join("first");
+    assert_eq!(tree(&replayed), tree(&original));
+    assert!(replayed.join("output/.gitkeep").is_file());
+    assert!(replayed.join("input/README.md").is_file());
+    assert!(replayed.join("src/paths.py").is_file());
+    assert!(fs::read_to_string(replayed.join(".env")).unwrap().contains("\nLAB_OUTPUT_DIR=\"output\""));
+    for file in ["yuuskel.toml", "LICENSE", "src/pyproject.toml", "env.sh", "paths.json"] {
+        assert_eq!(
+            fs::read_to_string(replayed.join(file)).unwrap(),
+            fs::read_to_string(original.join(file)).unwrap(),
+            "{}",
+            file
+        );
+    }
+}
+
+#[test]
+fn flags_override_answers() {
+    let sandbox = Sandbox::new();
+    fs::write(
+        sandbox.work().join("answers.toml"),
+        "mode = \"new\"\nname = \"from_file\"\nprefix = \"FILE\"\nstack = \"none\"\ngit = false\nlicense = \"none\"\n"
+    ).unwrap();
+
+    let output = sandbox.run(&["--answers", "answers.toml", "--name", "from_flag", "--no-prefix"]);
+    assert_success(&output);
+
+    let root = sandbox.work().join("from_flag");
+    assert!(!sandbox.work().join("from_file").exists());
+    assert!(fs::read_to_string(root.join(".env")).unwrap().contains("\nOUTPUT_DIR="));
+}
+
+#[test]
+fn invalid_answer() {
+    let sandbox = Sandbox::new();
+    fs::write(sandbox.work().join("answers.toml"), "name = \"proj\"\nstack = \"cobol\"\n").unwrap();
+
+    let output = sandbox.run(&["--answers", "answers.toml", "--yes"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid answer for stack: 'cobol'"), "{}", stderr);
+}
+
+#[test]
+fn unknown_key_is_a_config_error() {
+    let sandbox = Sandbox::new();
+    fs::write(sandbox.work().join("answers.toml"), "stakc = \"python\"\n").unwrap();
+
+    let output = sandbox.run(&["--answers", "answers.toml", "--yes"]);
+    assert_eq!(output.status.code(), Some(3));
+}
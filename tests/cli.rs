@@ -0,0 +1,484 @@
+// 命令行的端到端测试：在临时目录中运行 yuuskel，答案全部通过参数给出，git 由 PATH 中的假 git 代替
+#![cfg(unix)]
+
+mod common;
+
+use std::fs;
+
+use common::{ assert_success, read, tree, Sandbox };
+
+// 除被测项外全部给出答案，避免任何交互
+const BASE: [&str; 5] = ["--no-git", "--license", "none", "--no-stack", "--yes"];
+
+fn args<'a>(extra: &[&'a str]) -> Vec<&'a str> {
+    extra.iter().copied().chain(BASE).collect()
+}
+
+#[test]
+fn fresh_create() {
+    let sandbox = Sandbox::new();
+    let output = sandbox.run(&args(&["--name", "proj", "--no-prefix"]));
+    assert_success(&output);
+
+    let root = sandbox.work().join("proj");
+    insta::assert_snapshot!("fresh_tree", tree(&root));
+    insta::assert_snapshot!("fresh_env", read(&root, ".env"));
+    insta::assert_snapshot!("fresh_gitignore", read(&root, ".gitignore"));
+    insta::assert_snapshot!("fresh_metadata", read(&root, "yuuskel.toml"));
+    insta::assert_snapshot!("fresh_readme", read(&root, "README.md"));
+}
+
+#[test]
+fn rerun_in_existing_dir() {
+    let sandbox = Sandbox::new();
+    assert_success(&sandbox.run(&args(&["--name", "proj", "--no-prefix"])));
+    let root = sandbox.work().join("proj");
+
+    // 用户的改动：自己的变量、过期的路径、手改的 README、删掉的目录
+    let env = fs::read_to_string(root.join(".env")).unwrap();
+    let env = env.replace(&format!("{}/output", root.display()), "/old/place/output");
+    fs::write(root.join(".env"), format!("{}API_TOKEN=secret\n", env)).unwrap();
+    fs::write(root.join("README.md"), "# My own README\n").unwrap();
+    fs::remove_dir(root.join("logs")).unwrap();
+
+    let output = sandbox.run_in(&root, &args(&["--here", "--no-prefix"]));
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Project structure incrementally updated"), "{}", stdout);
+
+    assert!(root.join("logs").is_dir());
+    assert_eq!(fs::read_to_string(root.join("README.md")).unwrap(), "# My own README\n");
+    insta::assert_snapshot!("rerun_env", read(&root, ".env"));
+    insta::assert_snapshot!("rerun_tree", tree(&root));
+}
+
+#[test]
+fn rerun_without_changes_is_noop() {
+    let sandbox = Sandbox::new();
+    assert_success(&sandbox.run(&args(&["--name", "proj", "--no-prefix"])));
+    let root = sandbox.work().join("proj");
+
+    let output = sandbox.run_in(&root, &args(&["--here", "--no-prefix", "--dry-run"]));
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("(no changes needed)"), "{}", stdout);
+}
+
+#[test]
+fn rerun_keeps_recorded_template() {
+    let sandbox = Sandbox::new();
+    let template = sandbox.work().join("tpl");
+    fs::create_dir(&template).unwrap();
+    let manifest = "name = \"tpl\"\ndirs = [\"data\"]\nenv = [{ key = \"DATA_DIR\", path = \"data\" }]\n";
+    fs::write(template.join("template.toml"), manifest).unwrap();
+    assert_success(&sandbox.run(&args(&["--name", "proj", "--no-prefix", "--template", "tpl"])));
+    let root = sandbox.work().join("proj");
+
+    // 不给 --template 时沿用 yuuskel.toml 中记录的模板，而不是 default
+    let output = sandbox.run_in(&root, &args(&["--here", "--no-prefix", "--dry-run"]));
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("tpl"), "{}", stdout);
+    assert!(stdout.contains("(no changes needed)"), "{}", stdout);
+
+    assert_success(&sandbox.run_in(&root, &args(&["--here", "--no-prefix"])));
+    assert!(root.join("data").is_dir());
+    assert!(!root.join("input").exists());
+    let env = read(&root, ".env");
+    assert!(env.contains("DATA_DIR="), "{}", env);
+    assert!(!env.contains("INPUT_DIR="), "{}", env);
+}
+
+#[test]
+fn prefixed() {
+    let sandbox = Sandbox::new();
+    assert_success(&sandbox.run(&args(&["--name", "proj", "--prefix", "demo"])));
+
+    let root = sandbox.work().join("proj");
+    insta::assert_snapshot!("prefixed_env", read(&root, ".env"));
+    insta::assert_snapshot!("prefixed_usage", read(&root, "USAGE.md"));
+    assert!(read(&root, "yuuskel.toml").contains("prefix = \"DEMO\""));
+}
+
+#[test]
+fn rerun_keeps_recorded_prefix() {
+    let sandbox = Sandbox::new();
+    assert_success(&sandbox.run(&args(&["--name", "proj", "--prefix", "aaa"])));
+    let root = sandbox.work().join("proj");
+    let env = read(&root, ".env");
+
+    // 更改前缀被拒绝，什么都不写
+    let output = sandbox.run_in(&root, &args(&["--here", "--prefix", "bbb"]));
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("\"AAA\"") && stderr.contains("\"BBB\""), "{}", stderr);
+    assert_eq!(read(&root, ".env"), env);
+
+    // 不给前缀时沿用记录的前缀，不会出现其他前缀的变量
+    assert_success(&sandbox.run_in(&root, &args(&["--here"])));
+    assert_eq!(read(&root, ".env"), env);
+    assert!(!env.contains("BBB_"));
+    assert!(read(&root, "yuuskel.toml").contains("prefix = \"AAA\""));
+}
+
+#[test]
+fn rerun_changes_env_paths() {
+    let sandbox = Sandbox::new();
+    assert_success(&sandbox.run(&args(&["--name", "proj", "--no-prefix"])));
+    let root = sandbox.work().join("proj");
+
+    // 改为相对路径后记录随之更新
+    assert_success(&sandbox.run_in(&root, &args(&["--here", "--no-prefix", "--env-paths", "relative"])));
+    assert!(read(&root, "yuuskel.toml").contains("env_paths = \"relative\""));
+    let env = read(&root, ".env");
+    assert!(env.contains("OUTPUT_DIR=\"output\""), "{}", env);
+
+    // 移动后 relocate 按记录的相对路径处理，无需改动
+    let moved = sandbox.work().join("moved");
+    fs::rename(&root, &moved).unwrap();
+    let output = sandbox.run_in(&moved, &["relocate"]);
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("already matches"), "{}", stdout);
+    assert_eq!(read(&moved, ".env"), env);
+}
+
+#[test]
+fn failed_run_is_rolled_back() {
+    let sandbox = Sandbox::new();
+    assert_success(&sandbox.run(&args(&["--name", "proj", "--no-prefix"])));
+    let root = sandbox.work().join("proj");
+
+    // 过期的 .env 会先被改写；.gitignore 换成同名目录，写入时失败
+    let env = read(&root, ".env").replace("[ROOT]/output", "/old/place/output");
+    fs::write(root.join(".env"), env.replace("[ROOT]", &root.display().to_string())).unwrap();
+    let env = fs::read_to_string(root.join(".env")).unwrap();
+    fs::remove_dir(root.join("logs")).unwrap();
+    fs::remove_file(root.join(".gitignore")).unwrap();
+    fs::create_dir(root.join(".gitignore")).unwrap();
+    let before = tree(&root);
+
+    let output = sandbox.run_in(&root, &args(&["--here", "--no-prefix", "--env-format", "sh"]));
+    assert_eq!(output.status.code(), Some(5));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Rolled back the changes made so far"), "{}", stderr);
+    for line in ["restored .env", "removed env.sh", "removed logs"] {
+        assert!(stderr.contains(line), "{}: {}", line, stderr);
+    }
+
+    assert_eq!(fs::read_to_string(root.join(".env")).unwrap(), env);
+    assert!(!root.join("env.sh").exists());
+    assert!(!root.join("logs").exists());
+    assert_eq!(tree(&root), before);
+}
+
+#[test]
+fn env_formats() {
+    let sandbox = Sandbox::new();
+    let formats = ["--env-format", "sh,ps1,fish,direnv,json,toml"];
+    assert_success(&sandbox.run(&args(&[&["--name", "proj", "--prefix", "demo"], &formats[..]].concat())));
+
+    let root = sandbox.work().join("proj");
+    insta::assert_snapshot!("env_format_sh", read(&root, "env.sh"));
+    insta::assert_snapshot!("env_format_ps1", read(&root, "env.ps1"));
+    insta::assert_snapshot!("env_format_fish", read(&root, "env.fish"));
+    insta::assert_snapshot!("env_format_direnv", read(&root, ".envrc"));
+    insta::assert_snapshot!("env_format_json", read(&root, "paths.json"));
+    insta::assert_snapshot!("env_format_toml", read(&root, "paths.toml"));
+}
+
+#[test]
+fn rerun_syncs_env_formats() {
+    let sandbox = Sandbox::new();
+    assert_success(&sandbox.run(&args(&["--name", "proj", "--no-prefix", "--env-format", "sh,json"])));
+    let root = sandbox.work().join("proj");
+    let (sh, json) = (read(&root, "env.sh"), read(&root, "paths.json"));
+
+    // 移动后再次运行：记录的格式随 .env 一起按新位置重写
+    let moved = sandbox.work().join("moved");
+    fs::rename(&root, &moved).unwrap();
+    let output = sandbox.run_in(&moved, &args(&["--here", "--no-prefix"]));
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("env.sh") && stdout.contains("paths.json"), "{}", stdout);
+
+    assert_eq!(read(&moved, "env.sh"), sh);
+    assert_eq!(read(&moved, "paths.json"), json);
+    let raw = fs::read_to_string(moved.join("env.sh")).unwrap();
+    assert!(!raw.contains(&root.display().to_string()), "{}", raw);
+}
+
+#[test]
+fn helpers() {
+    let sandbox = Sandbox::new();
+    let helpers = ["--helper", "python,rust,julia,r"];
+    assert_success(&sandbox.run(&args(&[&["--name", "proj", "--prefix", "demo"], &helpers[..]].concat())));
+
+    let root = sandbox.work().join("proj");
+    insta::assert_snapshot!("helper_python", read(&root, "src/paths.py"));
+    insta::assert_snapshot!("helper_rust", read(&root, "src/paths.rs"));
+    insta::assert_snapshot!("helper_julia", read(&root, "src/paths.jl"));
+    insta::assert_snapshot!("helper_r", read(&root, "src/paths.R"));
+}
+
+#[test]
+fn license() {
+    let sandbox = Sandbox::new();
+    let output = sandbox.run(
+        &["--name", "proj", "--no-prefix", "--no-git", "--license", "MIT", "--holder", "Jane Doe", "--no-stack", "--yes"]
+    );
+    assert_success(&output);
+
+    let root = sandbox.work().join("proj");
+    insta::assert_snapshot!("license_mit", read(&root, "LICENSE"));
+    let metadata = read(&root, "yuuskel.toml");
+    assert!(metadata.contains("license = \"MIT\""), "{}", metadata);
+    assert!(metadata.contains("holder = \"Jane Doe\""), "{}", metadata);
+
+    // 已有 LICENSE 不会被覆盖
+    fs::write(root.join("LICENSE"), "custom\n").unwrap();
+    let output = sandbox.run_in(
+        &root,
+        &["--here", "--no-prefix", "--no-git", "--license", "Apache-2.0", "--no-stack", "--yes"]
+    );
+    assert_success(&output);
+    assert_eq!(fs::read_to_string(root.join("LICENSE")).unwrap(), "custom\n");
+}
+
+#[test]
+fn rerun_saves_answers() {
+    let sandbox = Sandbox::new();
+    let create = ["--name", "proj", "--no-prefix", "--no-git", "--license", "MIT", "--holder", "Jane Doe"];
+    assert_success(&sandbox.run(&[&create[..], &["--no-stack", "--yes"]].concat()));
+    let root = sandbox.work().join("proj");
+    let rerun = |extra: &[&str]| {
+        let output = sandbox.run_in(&root, &[&["--here", "--no-prefix", "--no-git", "--no-stack", "--yes"], extra].concat());
+        assert_success(&output);
+        read(&root, "yuuskel.toml")
+    };
+
+    // 不给 --license 时沿用记录的许可证与持有人
+    let metadata = rerun(&[]);
+    assert!(metadata.contains("license = \"MIT\""), "{}", metadata);
+    assert!(metadata.contains("holder = \"Jane Doe\""), "{}", metadata);
+
+    // 新的许可证
+    let metadata = rerun(&["--license", "Apache-2.0"]);
+    assert!(metadata.contains("license = \"Apache-2.0\""), "{}", metadata);
+    assert!(metadata.contains("holder = \"Jane Doe\""), "{}", metadata);
+
+    // 新的持有人
+    let metadata = rerun(&["--license", "Apache-2.0", "--holder", "John Roe"]);
+    assert!(metadata.contains("holder = \"John Roe\""), "{}", metadata);
+
+    // 配置中新增的额外目录
+    let config = sandbox.path().join("config/yuuskel");
+    fs::create_dir_all(&config).unwrap();
+    fs::write(config.join("config.toml"), "extra_dirs = [\"scratch\"]\n").unwrap();
+    let metadata = rerun(&[]);
+    assert!(root.join("scratch").is_dir());
+    assert!(metadata.contains("\"scratch\""), "{}", metadata);
+    assert!(metadata.contains("license = \"Apache-2.0\""), "{}", metadata);
+}
+
+#[test]
+fn git_init_and_commit() {
+    let sandbox = Sandbox::new();
+    let output = sandbox.run(
+        &["--name", "proj", "--no-prefix", "--git", "--commit", "--license", "none", "--no-stack", "--yes"]
+    );
+    assert_success(&output);
+
+    let root = sandbox.work().join("proj");
+    assert!(root.join(".git").is_dir());
+    assert!(read(&root, "yuuskel.toml").contains("git_initialized = true"));
+    let calls = sandbox.git_calls();
+    let steps: Vec<_> = calls
+        .iter()
+        .filter(|call| !call.starts_with("config"))
+        .map(String::as_str)
+        .collect();
+    insta::assert_debug_snapshot!("git_calls", steps);
+}
+
+#[test]
+fn existing_repo_is_not_committed() {
+    let sandbox = Sandbox::new();
+    let root = sandbox.work().join("proj");
+    fs::create_dir_all(root.join(".git")).unwrap();
+
+    // 已有仓库中既不 git add 也不提交：不会带上用户的改动，回滚时也不会在历史中留下提交
+    let git = ["--here", "--no-prefix", "--git", "--commit", "--license", "none", "--no-stack", "--yes"];
+    assert_success(&sandbox.run_in(&root, &git));
+
+    let calls = sandbox.git_calls();
+    assert!(calls.iter().any(|call| call == "init"), "{:?}", calls);
+    assert!(!calls.iter().any(|call| call.starts_with("add") || call.starts_with("commit")), "{:?}", calls);
+}
+
+#[test]
+fn no_git_leaves_git_alone() {
+    let sandbox = Sandbox::new();
+    assert_success(&sandbox.run(&args(&["--name", "proj", "--no-prefix"])));
+
+    assert!(!sandbox.work().join("proj").join(".git").exists());
+    assert!(sandbox.git_calls().iter().all(|call| call.starts_with("config")));
+}
+
+#[test]
+fn missing_answer_without_tty() {
+    let sandbox = Sandbox::new();
+    let output = sandbox.run(&["--name", "proj"]);
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("missing answer for --prefix/--no-prefix"), "{}", stderr);
+    assert!(!sandbox.work().join("proj").exists());
+}
+
+#[test]
+fn rejects_invalid_name() {
+    let sandbox = Sandbox::new();
+    let output = sandbox.run(&args(&["--name", "CON", "--no-prefix"]));
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(fs::read_dir(sandbox.work()).unwrap().next().is_none());
+}
+
+#[test]
+fn rejects_invalid_prefix() {
+    let sandbox = Sandbox::new();
+    for prefix in ["my-proj", "1abc", "a b"] {
+        let output = sandbox.run(&args(&["--name", "proj", "--prefix", prefix]));
+        assert_eq!(output.status.code(), Some(2), "{}", prefix);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("invalid env prefix"), "{}", stderr);
+        assert!(fs::read_dir(sandbox.work()).unwrap().next().is_none());
+    }
+}
+
+#[test]
+fn name_as_path() {
+    let sandbox = Sandbox::new();
+    assert_success(&sandbox.run(&args(&["--name", "exps/run1", "--no-prefix"])));
+    let root = sandbox.work().join("exps/run1");
+    assert!(read(&root, "README.md").starts_with("# run1"));
+
+    let absolute = sandbox.path().join("elsewhere/run2");
+    assert_success(&sandbox.run(&args(&["--name", &absolute.to_string_lossy(), "--no-prefix"])));
+    assert!(absolute.join("yuuskel.toml").is_file());
+
+    // 只校验最后一级
+    for name in ["exps/CON", "exps/..", "/"] {
+        let output = sandbox.run(&args(&["--name", name, "--no-prefix"]));
+        assert_eq!(output.status.code(), Some(2), "{}", name);
+    }
+}
+
+#[test]
+fn dry_run_writes_nothing() {
+    let sandbox = Sandbox::new();
+    let output = sandbox.run(&args(&["--name", "proj", "--no-prefix", "--dry-run"]));
+    assert_success(&output);
+
+    assert!(!sandbox.work().join("proj").exists());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(".env"), "{}", stdout);
+}
+
+// 前缀为 DEMO 的项目中：OUTPUT_DIR 换成了 LAB_ 前缀，ASSETS_DIR 与 SRC_DIR 被删除（或以多段前缀存在）
+fn broken_project(sandbox: &Sandbox) -> std::path::PathBuf {
+    assert_success(&sandbox.run(&args(&["--name", "proj", "--prefix", "demo"])));
+    let root = sandbox.work().join("proj");
+    let env = read(&root, ".env")
+        .replace("DEMO_OUTPUT_DIR=", "LAB_OUTPUT_DIR=")
+        .replace("DEMO_SRC_DIR=", "MY_LAB_SRC_DIR=")
+        .lines()
+        .filter(|line| !line.starts_with("DEMO_ASSETS_DIR="))
+        .map(|line| format!("{}\n", line))
+        .collect::<String>();
+    fs::write(root.join(".env"), env.replace("[ROOT]", &root.display().to_string())).unwrap();
+    root
+}
+
+#[test]
+fn check_reports_failures() {
+    let sandbox = Sandbox::new();
+    let root = broken_project(&sandbox);
+
+    let output = sandbox.run_in(&root, &["check"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("expected DEMO_OUTPUT_DIR but .env has LAB_OUTPUT_DIR (prefix mismatch)"), "{}", stdout);
+    // DEMO_TEMP_ASSETS_DIR 与 MY_LAB_SRC_DIR 不算前缀不一致
+    for key in ["DEMO_ASSETS_DIR", "DEMO_SRC_DIR"] {
+        assert!(stdout.contains(&format!("{} — variable is missing from .env", key)), "{}", stdout);
+    }
+    assert!(stdout.contains("3 of "), "{}", stdout);
+}
+
+#[test]
+fn check_reports_failures_as_json() {
+    let sandbox = Sandbox::new();
+    let root = broken_project(&sandbox);
+
+    let output = sandbox.run_in(&root, &["check", "--format", "json"]);
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["ok"], false);
+    let failed: Vec<(&str, &str)> = report["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|c| c["ok"] == false)
+        .map(|c| (c["check"].as_str().unwrap(), c["target"].as_str().unwrap()))
+        .collect();
+    assert_eq!(failed, [("prefix", "DEMO_OUTPUT_DIR"), ("env", "DEMO_ASSETS_DIR"), ("env", "DEMO_SRC_DIR")]);
+}
+
+#[test]
+fn check_uses_recorded_keys() {
+    let sandbox = Sandbox::new();
+    let template = sandbox.work().join("tpl");
+    fs::create_dir(&template).unwrap();
+    let manifest = "name = \"tpl\"\ndirs = [\"data\"]\nenv = [{ key = \"DATA_DIR\", path = \"data\" }]\n";
+    fs::write(template.join("template.toml"), manifest).unwrap();
+    assert_success(&sandbox.run(&args(&["--name", "proj", "--no-prefix", "--template", "tpl"])));
+    let root = sandbox.work().join("proj");
+    assert!(read(&root, "yuuskel.toml").contains("key = \"DATA_DIR\"\npath = \"data\""));
+
+    // 模板不再可用时仍按记录的变量检查
+    fs::remove_dir_all(&template).unwrap();
+    let output = sandbox.run_in(&root, &["check"]);
+    assert_success(&output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("DATA_DIR"));
+}
+
+#[test]
+fn relocate_uses_recorded_vars() {
+    let sandbox = Sandbox::new();
+    let template = sandbox.work().join("tpl");
+    fs::create_dir(&template).unwrap();
+    let manifest = "name = \"tpl\"\ndirs = [\"data\"]\nenv = [{ key = \"DATA_DIR\", path = \"data\" }]\n";
+    fs::write(template.join("template.toml"), manifest).unwrap();
+    assert_success(&sandbox.run(&args(&["--name", "proj", "--no-prefix", "--template", "tpl"])));
+    let root = sandbox.work().join("proj");
+
+    // 模板之后的改动不会混入：新增的变量不写入，改过的目录仍按记录的路径
+    let manifest = "name = \"tpl\"\ndirs = [\"raw\"]\nenv = [{ key = \"DATA_DIR\", path = \"raw\" }, { key = \"CACHE_DIR\", path = \"raw\" }]\n";
+    fs::write(template.join("template.toml"), manifest).unwrap();
+    let moved = sandbox.work().join("moved");
+    fs::rename(&root, &moved).unwrap();
+    assert_success(&sandbox.run_in(&moved, &["relocate"]));
+    let env = fs::read_to_string(moved.join(".env")).unwrap();
+    assert!(env.contains(&format!("DATA_DIR=\"{}/data\"", moved.display())), "{}", env);
+    assert!(!env.contains("CACHE_DIR"), "{}", env);
+
+    // 模板不再可用时同样可以同步
+    fs::remove_dir_all(&template).unwrap();
+    let again = sandbox.work().join("again");
+    fs::rename(&moved, &again).unwrap();
+    assert_success(&sandbox.run_in(&again, &["relocate"]));
+    let env = fs::read_to_string(again.join(".env")).unwrap();
+    assert!(env.contains(&format!("DATA_DIR=\"{}/data\"", again.display())), "{}", env);
+}
@@ -0,0 +1,160 @@
+// 集成测试共用的工具：临时目录中的沙盒、假的 git 与生成结果的快照
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{ Path, PathBuf };
+use std::process::{ Command, Output, Stdio };
+
+use chrono::Datelike;
+use tempfile::TempDir;
+
+// 每个测试独立的临时目录：work/ 为运行目录，bin/ 中放假的 git，config/、home/ 与 cache/ 隔离用户配置和缓存
+pub struct Sandbox {
+    dir: TempDir,
+}
+
+impl Sandbox {
+    pub fn new() -> Self {
+        let sandbox = Sandbox::with_real_git();
+        sandbox.install_fake_git();
+        sandbox
+    }
+
+    // 使用系统的 git（远程模板的测试需要真正克隆仓库）
+    pub fn with_real_git() -> Self {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        for sub in ["work", "bin", "config", "home", "cache"] {
+            fs::create_dir(dir.path().join(sub)).expect("create sandbox dir");
+        }
+        Sandbox { dir }
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    pub fn work(&self) -> PathBuf {
+        self.dir.path().join("work")
+    }
+
+    // 假的 git：记录每次调用的参数，init 时创建 .git，config 返回固定的用户信息
+    fn install_fake_git(&self) {
+        let script = format!(
+            r#"#!/bin/sh
+echo "$@" >> "{log}"
+case "$1" in
+  config)
+    case "$2" in
+      user.name) echo "Test User" ;;
+      user.email) echo "test@example.com" ;;
+    esac ;;
+  init) mkdir -p .git ;;
+esac
+exit 0
+"#,
+            log = self.git_log_path().display()
+        );
+        let path = self.dir.path().join("bin").join("git");
+        fs::write(&path, script).expect("write fake git");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).expect("chmod fake git");
+        }
+    }
+
+    fn git_log_path(&self) -> PathBuf {
+        self.dir.path().join("git.log")
+    }
+
+    // 假的 git 收到的命令（每行一次调用）
+    pub fn git_calls(&self) -> Vec<String> {
+        fs::read_to_string(self.git_log_path())
+            .unwrap_or_default()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    // 在 dir 中运行 yuuskel：界面语言固定为英文（--lang 放在最后，子命令也能接受），标准输入不是终端
+    pub fn run_in(&self, dir: &Path, args: &[&str]) -> Output {
+        let path = std::env::join_paths(
+            std::iter
+                ::once(self.dir.path().join("bin"))
+                .chain(std::env::split_paths(&std::env::var_os("PATH").unwrap_or_default()))
+        ).expect("join PATH");
+        Command::new(env!("CARGO_BIN_EXE_yuuskel"))
+            .args(args)
+            .args(["--lang", "en"])
+            .current_dir(dir)
+            .env("PATH", path)
+            .env("XDG_CONFIG_HOME", self.dir.path().join("config"))
+            .env("HOME", self.dir.path().join("home"))
+            .env("XDG_CACHE_HOME", self.dir.path().join("cache"))
+            .env_remove("APPDATA")
+            .env_remove("LOCALAPPDATA")
+            .env_remove("LC_ALL")
+            .env_remove("LC_MESSAGES")
+            .env("LANG", "C")
+            .env("NO_COLOR", "1")
+            .stdin(Stdio::null())
+            .output()
+            .expect("run yuuskel")
+    }
+
+    pub fn run(&self, args: &[&str]) -> Output {
+        self.run_in(&self.work(), args)
+    }
+}
+
+// 断言命令成功，失败时输出 stdout/stderr 便于排查
+pub fn assert_success(output: &Output) {
+    assert!(
+        output.status.success(),
+        "exit {:?}\n--- stdout ---\n{}\n--- stderr ---\n{}",
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+// 把与运行环境有关的内容替换为占位符：项目路径、年份、版本号
+pub fn redact(content: &str, root: &Path) -> String {
+    // 临时目录可能经过符号链接（如 macOS 的 /tmp），两种写法都替换
+    let canonical = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    content
+        .replace(&canonical.display().to_string(), "[ROOT]")
+        .replace(&root.display().to_string(), "[ROOT]")
+        .replace(&chrono::Local::now().year().to_string(), "[YEAR]")
+        .replace(env!("CARGO_PKG_VERSION"), "[VERSION]")
+}
+
+// 生成结果的目录树（排除 .git），目录以 / 结尾
+pub fn tree(root: &Path) -> String {
+    let mut entries = Vec::new();
+    walk(root, root, &mut entries);
+    entries.sort();
+    entries.join("\n")
+}
+
+fn walk(root: &Path, dir: &Path, entries: &mut Vec<String>) {
+    for entry in fs::read_dir(dir).expect("read dir") {
+        let path = entry.expect("read dir entry").path();
+        let relative = path.strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/");
+        if relative == ".git" {
+            continue;
+        }
+        if path.is_dir() {
+            entries.push(format!("{}/", relative));
+            walk(root, &path, entries);
+        } else {
+            entries.push(relative);
+        }
+    }
+}
+
+// 读取项目中的文件（已替换占位符）
+pub fn read(root: &Path, file: &str) -> String {
+    let content = fs::read_to_string(root.join(file)).unwrap_or_else(|e| panic!("read {}: {}", file, e));
+    redact(&content, root)
+}
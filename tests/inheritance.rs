@@ -0,0 +1,146 @@
+// 模板继承：extends 叠加目录、变量、.gitignore 片段与文件，并可用 `template show --resolved` 查看解析结果
+#![cfg(unix)]
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use common::{ assert_success, read, redact, tree, Sandbox };
+
+const BASE: [&str; 6] = ["--no-git", "--license", "none", "--no-stack", "--no-prefix", "--yes"];
+
+// 实验室的基础模板（继承内置 default）与其上的机器学习模板
+const LAB: &str = r#"name = "lab"
+extends = "default"
+description = "Lab base"
+dirs = ["data/raw", { path = "input", description = "Instrument exports" }]
+remove_dirs = ["assets"]
+rename_env = { OUTPUT_DIR = "RESULTS_DIR" }
+env = [{ key = "RAW_DIR", path = "data/raw" }]
+gitignore = ["python"]
+
+[[files]]
+path = "README.md"
+source = "readme.md"
+"#;
+
+const ML: &str = r#"name = "ml"
+extends = "../lab"
+dirs = ["models"]
+remove_dirs = ["notebooks"]
+rename_env = { RESULTS_DIR = "RUNS_DIR" }
+env = [{ key = "MODEL_DIR", path = "models" }, { key = "RAW_DIR", path = "data" }]
+gitignore = ["os", "julia"]
+"#;
+
+fn write_template(dir: &Path, manifest: &str, files: &[(&str, &str)]) {
+    fs::create_dir_all(dir.join("files")).unwrap();
+    fs::write(dir.join("template.toml"), manifest).unwrap();
+    for (name, content) in files {
+        fs::write(dir.join("files").join(name), content).unwrap();
+    }
+}
+
+fn setup(sandbox: &Sandbox) {
+    let readme = "# {{ project_name }}\n\nResults go to ${{ env_keys.OUTPUT_DIR }}.\n";
+    write_template(&sandbox.work().join("templates/lab"), LAB, &[("readme.md", readme)]);
+    write_template(&sandbox.work().join("templates/ml"), ML, &[]);
+}
+
+#[test]
+fn overlays_are_applied_in_order() {
+    let sandbox = Sandbox::new();
+    setup(&sandbox);
+    let output = sandbox.run(&[&["--name", "proj", "--template", "templates/ml"], &BASE[..]].concat());
+    assert_success(&output);
+
+    let root = sandbox.work().join("proj");
+    insta::assert_snapshot!("inherited_tree", tree(&root));
+    insta::assert_snapshot!("inherited_env", read(&root, ".env"));
+    // README 来自 lab，USAGE.md 来自 default；重命名两次的变量仍可用原名引用
+    assert_eq!(read(&root, "README.md"), "# proj\n\nResults go to $RUNS_DIR.\n");
+    assert!(root.join("USAGE.md").is_file());
+    let gitignore = read(&root, ".gitignore");
+    for rule in ["__pycache__/", ".DS_Store", "*.jl.cov", "\ninput/\n", "\noutput/\n"] {
+        assert!(gitignore.contains(rule), "{}", gitignore);
+    }
+    // 删除的目录不再出现在忽略规则中
+    assert!(!gitignore.contains("assets/"), "{}", gitignore);
+}
+
+#[test]
+fn show_resolved() {
+    let sandbox = Sandbox::new();
+    setup(&sandbox);
+
+    let output = sandbox.run(&["template", "show", "templates/ml", "--resolved"]);
+    assert_success(&output);
+    let resolved = redact(&String::from_utf8_lossy(&output.stdout), &sandbox.work());
+    insta::assert_snapshot!("resolved_ml", resolved);
+
+    // 解析结果是确定的
+    let again = sandbox.run(&["template", "show", "templates/ml", "--resolved"]);
+    assert_eq!(output.stdout, again.stdout);
+
+    // 不加 --resolved 时原样输出
+    let output = sandbox.run(&["template", "show", "templates/ml"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), ML);
+}
+
+#[test]
+fn circular_extends_is_a_template_error() {
+    let sandbox = Sandbox::new();
+    write_template(&sandbox.work().join("a"), "name = \"a\"\nextends = \"../b\"\n", &[]);
+    write_template(&sandbox.work().join("b"), "name = \"b\"\nextends = \"../a\"\n", &[]);
+
+    let output = sandbox.run(&[&["--name", "proj", "--template", "a"], &BASE[..]].concat());
+    assert_eq!(output.status.code(), Some(4));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("circular extends"));
+    assert!(!sandbox.work().join("proj").exists());
+}
+
+#[test]
+fn unknown_overrides_are_template_errors() {
+    let sandbox = Sandbox::new();
+    for (name, manifest, expected) in [
+        ("dirs", "name = \"x\"\nextends = \"default\"\nremove_dirs = [\"nope\"]\n", "'nope' is not an inherited directory"),
+        ("env", "name = \"x\"\nextends = \"default\"\nrename_env = { NOPE = \"X\" }\n", "no inherited variable 'NOPE'"),
+        ("standalone", "name = \"x\"\nremove_dirs = [\"input\"]\n", "require extends"),
+    ] {
+        write_template(&sandbox.work().join(name), manifest, &[]);
+        let output = sandbox.run(&["template", "show", name, "--resolved"]);
+        assert_eq!(output.status.code(), Some(4), "{}", name);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(expected), "{}: {}", name, stderr);
+    }
+}
+
+#[test]
+fn paths_outside_the_project_are_template_errors() {
+    let sandbox = Sandbox::new();
+    for (name, manifest, expected) in [
+        ("dir", "name = \"x\"\ndirs = [\"../escaped_dir\"]\n", "dirs: '../escaped_dir'"),
+        ("abs_dir", "name = \"x\"\ndirs = [\"/tmp/escaped_dir\"]\n", "dirs: '/tmp/escaped_dir'"),
+        ("env", "name = \"x\"\nenv = [{ key = \"X_DIR\", path = \"a/../../x\" }]\n", "env: 'a/../../x'"),
+        (
+            "file",
+            "name = \"x\"\n[[files]]\npath = \"../escaped.txt\"\nsource = \"a.md\"\n",
+            "files.path: '../escaped.txt'",
+        ),
+        (
+            "source",
+            "name = \"x\"\n[[files]]\npath = \"a.md\"\nsource = \"../../../../etc/hostname\"\n",
+            "files.source: '../../../../etc/hostname'",
+        ),
+    ] {
+        write_template(&sandbox.work().join(name), manifest, &[("a.md", "a")]);
+        let output = sandbox.run(&[&["--name", "proj", "--template", name], &BASE[..]].concat());
+        assert_eq!(output.status.code(), Some(4), "{}", name);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(expected), "{}: {}", name, stderr);
+        assert!(!sandbox.work().join("proj").exists(), "{}", name);
+    }
+    assert!(!sandbox.work().join("escaped_dir").exists());
+    assert!(!sandbox.work().join("escaped.txt").exists());
+}
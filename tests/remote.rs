@@ -0,0 +1,129 @@
+// 远程模板：以临时目录中的裸仓库作为远程，验证克隆、按提交固定与 `yuuskel template` 子命令
+#![cfg(unix)]
+
+mod common;
+
+use std::fs;
+use std::path::{ Path, PathBuf };
+use std::process::Command;
+
+use common::{ assert_success, Sandbox };
+
+const MANIFEST: &str = r#"name = "lab"
+description = "Lab layout"
+dirs = ["data/raw", "results"]
+env = [{ key = "RAW_DIR", path = "data/raw" }]
+"#;
+
+// 运行系统的 git，返回标准输出
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.name=Test User", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("run git");
+    assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+// 远程仓库：sandbox 中的 remote.git（裸仓库）与用于提交的 template/ 工作区
+struct Remote {
+    work: PathBuf,
+    url: String,
+}
+
+impl Remote {
+    fn new(sandbox: &Sandbox) -> Remote {
+        let bare = sandbox.path().join("remote.git");
+        let work = sandbox.path().join("template");
+        git(sandbox.path(), &["init", "--quiet", "--bare", "-b", "main", "remote.git"]);
+        git(sandbox.path(), &["init", "--quiet", "-b", "main", "template"]);
+        let remote = Remote { work, url: format!("git+file://{}", bare.display()) };
+        remote.publish(MANIFEST);
+        remote
+    }
+
+    // 提交新的 template.toml 并推送，返回提交哈希
+    fn publish(&self, manifest: &str) -> String {
+        fs::write(self.work.join("template.toml"), manifest).unwrap();
+        git(&self.work, &["add", "."]);
+        git(&self.work, &["commit", "--quiet", "-m", "template"]);
+        git(&self.work, &["push", "--quiet", "../remote.git", "main"]);
+        git(&self.work, &["rev-parse", "HEAD"])
+    }
+}
+
+fn init(sandbox: &Sandbox, name: &str, template: &str) {
+    assert_success(&sandbox.run(&["--yes", "--name", name, "--no-git", "--template", template]));
+}
+
+#[test]
+fn init_pins_the_commit() {
+    let sandbox = Sandbox::with_real_git();
+    let remote = Remote::new(&sandbox);
+    let commit = git(&remote.work, &["rev-parse", "HEAD"]);
+
+    init(&sandbox, "proj", &remote.url);
+
+    let project = sandbox.work().join("proj");
+    assert!(project.join("data/raw").is_dir());
+    let metadata = fs::read_to_string(project.join("yuuskel.toml")).unwrap();
+    assert!(metadata.contains(&format!("template = \"{}\"", remote.url)), "{}", metadata);
+    assert!(metadata.contains(&format!("template_commit = \"{}\"", commit)), "{}", metadata);
+    assert!(sandbox.path().join("cache/yuuskel/templates").read_dir().unwrap().next().is_some());
+}
+
+#[test]
+fn upgrade_keeps_the_pinned_commit() {
+    let sandbox = Sandbox::with_real_git();
+    let remote = Remote::new(&sandbox);
+    init(&sandbox, "old", &remote.url);
+
+    let newer = remote.publish(&MANIFEST.replace(r#""results"]"#, r#""results", "notebooks"]"#));
+    let output = sandbox.run(&["template", "update"]);
+    assert_success(&output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains(&newer[..7]));
+
+    // 已有项目仍按固定的提交升级，新项目使用更新后的模板
+    assert_success(&sandbox.run_in(&sandbox.work().join("old"), &["upgrade"]));
+    assert!(!sandbox.work().join("old/notebooks").exists());
+    init(&sandbox, "new", &remote.url);
+    assert!(sandbox.work().join("new/notebooks").is_dir());
+    let metadata = fs::read_to_string(sandbox.work().join("new/yuuskel.toml")).unwrap();
+    assert!(metadata.contains(&newer), "{}", metadata);
+}
+
+#[test]
+fn list_and_remove() {
+    let sandbox = Sandbox::with_real_git();
+    let remote = Remote::new(&sandbox);
+    init(&sandbox, "proj", &remote.url);
+
+    let output = sandbox.run(&["template", "list"]);
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("default"), "{}", stdout);
+    assert!(stdout.contains(&remote.url) && stdout.contains("lab"), "{}", stdout);
+
+    assert_success(&sandbox.run(&["template", "remove", &remote.url]));
+    let output = sandbox.run(&["template", "list"]);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains(&remote.url));
+
+    // 缓存删除后按固定的提交重新克隆
+    assert_success(&sandbox.run_in(&sandbox.work().join("proj"), &["upgrade"]));
+    let output = sandbox.run(&["template", "list"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains(&remote.url));
+
+    let output = sandbox.run(&["template", "remove", "git+file:///nowhere.git"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn unknown_ref_is_a_template_error() {
+    let sandbox = Sandbox::with_real_git();
+    let remote = Remote::new(&sandbox);
+    let output = sandbox.run(&["--yes", "--name", "proj", "--template", &format!("{}#v9", remote.url)]);
+    assert_eq!(output.status.code(), Some(4));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("'v9'"));
+}
@@ -0,0 +1,202 @@
+// 库接口的测试：通过 Skeleton 直接给出全部答案，不经过终端
+mod common;
+
+use std::fs;
+
+use common::{ read, tree };
+use yuuskel::skeleton::{ validate_prefix, validate_project_name };
+use yuuskel::stack::Stack;
+use yuuskel::plan::Interrupt;
+use yuuskel::template::Template;
+use yuuskel::metadata::Metadata;
+use yuuskel::{ license, Language, Skeleton, UpgradeOptions, YuuskelError };
+
+#[test]
+fn project_names() {
+    for name in ["my_project", "data-2024", "项目", "a.b"] {
+        assert!(validate_project_name(name), "{} should be accepted", name);
+    }
+    for name in ["", ".", "..", ".hidden", " padded", "trailing ", "a/b", "a\\b", "con", "LPT1"] {
+        assert!(!validate_project_name(name), "{:?} should be rejected", name);
+    }
+    assert!(validate_project_name(&"x".repeat(100)));
+    assert!(!validate_project_name(&"x".repeat(101)));
+}
+
+#[test]
+fn builder_normalizes_prefix() {
+    let dir = tempfile::tempdir().unwrap();
+    let spec = Skeleton::new(dir.path()).prefix(" demo_ ").build().unwrap();
+    assert_eq!(spec.prefix.as_deref(), Some("DEMO"));
+    let spec = Skeleton::new(dir.path()).prefix("").build().unwrap();
+    assert_eq!(spec.prefix, None);
+
+    for prefix in ["my-proj", "1ABC", "é"] {
+        let result = Skeleton::new(dir.path()).prefix(prefix).build();
+        assert!(matches!(result, Err(YuuskelError::Validation(_))), "{}", prefix);
+    }
+    for prefix in ["_PRIVATE", "lab2", "A_B"] {
+        assert!(validate_prefix(prefix), "{}", prefix);
+    }
+}
+
+#[test]
+fn generate_and_regenerate() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("proj");
+    let spec = Skeleton::new(&root)
+        .prefix("lab")
+        .dir("data/raw")
+        .env_var("RAW_DIR", "data/raw")
+        .license(license::find("MIT"), Some("Jane Doe".to_string()))
+        .gitkeep(true)
+        .build()
+        .unwrap();
+
+    let report = yuuskel::generate(&spec).unwrap();
+    assert!(report.updated.is_empty() && report.skipped.is_empty());
+    assert!(report.created.contains(&"data/raw".to_string()));
+    assert!(report.created.contains(&"LICENSE".to_string()));
+    insta::assert_snapshot!("library_tree", tree(&root));
+    insta::assert_snapshot!("library_env", read(&root, ".env"));
+
+    // 再次生成：已有文件全部跳过，.env 中用户的行保留、过期的路径被更新
+    let env = fs::read_to_string(root.join(".env")).unwrap();
+    let stale = env.replace(&format!("{}/data/raw", root.display()), "/elsewhere/data/raw");
+    fs::write(root.join(".env"), format!("# mine\n{}EXTRA=1\n", stale)).unwrap();
+
+    let report = yuuskel::generate(&spec).unwrap();
+    assert!(report.created.is_empty(), "{:?}", report.created);
+    assert_eq!(report.updated, [".env"]);
+    assert!(report.skipped.contains(&"LICENSE".to_string()));
+    let change = &report.env_changes[0];
+    assert_eq!(change.key, "LAB_RAW_DIR");
+    assert_eq!(change.old.as_deref(), Some("/elsewhere/data/raw"));
+    let env = read(&root, ".env");
+    assert!(env.starts_with("# mine\n"), "{}", env);
+    assert!(env.ends_with("EXTRA=1\n"), "{}", env);
+    assert!(env.contains("LAB_RAW_DIR=\"[ROOT]/data/raw\""), "{}", env);
+}
+
+#[test]
+fn plan_only_reads() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("proj");
+    let plan = Skeleton::new(&root).build().unwrap().plan().unwrap();
+
+    assert!(!plan.actions.is_empty());
+    assert!(!root.exists());
+}
+
+#[test]
+fn localized_documents() {
+    for (lang, stack_heading, dir_footer) in [
+        (Language::ZhHant, "## 🐍 Python 技術堆疊", "由 yuuskel 產生"),
+        (Language::Ja, "## 🐍 Python スタック", "yuuskel により生成"),
+    ] {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("proj");
+        let spec = Skeleton::new(&root)
+            .language(lang)
+            .stack(Some(Stack::Python))
+            .dir_readmes(true)
+            .build()
+            .unwrap();
+        yuuskel::generate(&spec).unwrap();
+
+        assert!(read(&root, "USAGE.md").contains(stack_heading), "{}", lang.code());
+        assert!(read(&root, "input/README.md").contains(dir_footer), "{}", lang.code());
+    }
+    let dir = tempfile::tempdir().unwrap();
+    let spec = Skeleton::new(dir.path()).language(Language::ZhHant).build().unwrap();
+    yuuskel::generate(&spec).unwrap();
+    assert!(read(dir.path(), "README.md").contains("## 📁 目錄結構"));
+
+    // 模板中繁体中文缺少的文件回退到简体中文
+    let template = tempfile::tempdir().unwrap();
+    let manifest = "name = \"zh-only\"\n[[files]]\npath = \"README.md\"\nsource = \"readme.{lang}.md\"\n";
+    fs::write(template.path().join("template.toml"), manifest).unwrap();
+    fs::create_dir(template.path().join("files")).unwrap();
+    fs::write(template.path().join("files/readme.zh.md"), "# 简体\n").unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let template = Template::load(&template.path().display().to_string()).unwrap();
+    let spec = Skeleton::new(dir.path()).template(template).language(Language::ZhHant).build().unwrap();
+    yuuskel::generate(&spec).unwrap();
+    assert_eq!(fs::read_to_string(dir.path().join("README.md")).unwrap(), "# 简体\n");
+}
+
+#[test]
+fn interrupt_rolls_back() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("proj");
+    let plan = Skeleton::new(&root).build().unwrap().plan().unwrap();
+
+    // 第一个动作完成后请求中断：已创建的根目录被删除
+    let interrupt = Interrupt::new();
+    let result = yuuskel::skeleton::apply(&plan, &interrupt, |_, _| {
+        assert!(interrupt.request());
+    });
+    let Err(rollback) = result else { panic!("expected an interrupted run") };
+    assert!(matches!(rollback.error, YuuskelError::Interrupted));
+    assert!(!root.exists());
+
+    // 中断只影响传入它的调用
+    assert!(!Interrupt::new().is_requested());
+    yuuskel::generate(&Skeleton::new(&root).build().unwrap()).unwrap();
+    assert!(root.join(".env").is_file());
+}
+
+#[test]
+fn license_placeholders_only_in_copyright_line() {
+    let mit = license::find("MIT").unwrap().render(2024, "Jane Doe");
+    assert!(mit.contains("\nCopyright (c) 2024 Jane Doe\n"), "{}", mit);
+    assert!(!mit.contains("[year]") && !mit.contains("[fullname]"));
+
+    // GPL 系列与 Apache 的占位符只在附录的使用说明中，原样保留
+    for id in ["GPL-3.0", "AGPL-3.0", "Apache-2.0"] {
+        let license = license::find(id).unwrap();
+        let text = license.render(2024, "Jane Doe");
+        assert_eq!(text, license.text, "{}", id);
+        assert!(!text.contains("Jane Doe"), "{}", id);
+    }
+}
+
+#[test]
+fn extra_paths_stay_inside_the_project() {
+    let dir = tempfile::tempdir().unwrap();
+    for spec in [
+        Skeleton::new(dir.path()).dir("../outside"),
+        Skeleton::new(dir.path()).env_var("X_DIR", "/etc"),
+    ] {
+        assert!(matches!(spec.build(), Err(YuuskelError::Validation(_))));
+    }
+}
+
+#[test]
+fn upgrade_and_relocate_plans() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("proj");
+    yuuskel::generate(&Skeleton::new(&root).build().unwrap()).unwrap();
+
+    // 升级：补上删掉的目录与额外目录，计划最后写回记录
+    fs::remove_dir(root.join("logs")).unwrap();
+    let options = UpgradeOptions { extra_dirs: vec!["scratch".to_string()], ..UpgradeOptions::default() };
+    let metadata = Metadata::load(&root).unwrap().unwrap();
+    let plan = yuuskel::plan_upgrade(&root, metadata, &options).unwrap();
+    assert!(!root.join("logs").exists());
+    assert!(yuuskel::skeleton::apply(&plan, &Interrupt::new(), |_, _| {}).is_ok());
+    assert!(root.join("logs").is_dir() && root.join("scratch").is_dir());
+    assert!(Metadata::load(&root).unwrap().unwrap().dirs.contains(&"scratch".to_string()));
+
+    // 移动后重写 .env 中的路径
+    let moved = dir.path().join("moved");
+    fs::rename(&root, &moved).unwrap();
+    let metadata = Metadata::load(&moved).unwrap().unwrap();
+    let plan = yuuskel::plan_relocate(&moved, metadata, None).unwrap();
+    assert!(plan.has_changes());
+    assert!(yuuskel::skeleton::apply(&plan, &Interrupt::new(), |_, _| {}).is_ok());
+    let env = fs::read_to_string(moved.join(".env")).unwrap();
+    assert!(env.contains(&format!("OUTPUT_DIR=\"{}/output\"", moved.display())), "{}", env);
+    let metadata = Metadata::load(&moved).unwrap().unwrap();
+    assert!(!yuuskel::plan_relocate(&moved, metadata, None).unwrap().has_changes());
+}